@@ -0,0 +1,201 @@
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE},
+    Engine,
+};
+use docker_api::opts::RegistryAuth;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+pub(crate) const DOCKER_HUB_ADDRESS: &str = "https://index.docker.io/v1/";
+
+#[derive(Debug, Default, Deserialize)]
+struct DockerConfig {
+    #[serde(default)]
+    auths: HashMap<String, AuthEntry>,
+    #[serde(rename = "credsStore")]
+    creds_store: Option<String>,
+    #[serde(default, rename = "credHelpers")]
+    cred_helpers: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthEntry {
+    auth: Option<String>,
+    identitytoken: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct HelperCredentials {
+    username: String,
+    secret: String,
+}
+
+// credential helpers are processes waited for synchronously, so the lookup runs off the async
+// workers
+pub(crate) async fn lookup(server_address: &str) -> Option<RegistryAuth> {
+    let server_address = server_address.to_string();
+    tokio::task::spawn_blocking(move || lookup_blocking(&server_address))
+        .await
+        .ok()
+        .flatten()
+}
+
+// DOCKER_AUTH_CONFIG has the same format as the docker config file and takes precedence
+fn lookup_blocking(server_address: &str) -> Option<RegistryAuth> {
+    let from_env = std::env::var("DOCKER_AUTH_CONFIG")
+        .ok()
+        .and_then(|config| serde_json::from_str::<DockerConfig>(&config).ok());
+    let from_file = docker_config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|config| serde_json::from_str::<DockerConfig>(&config).ok());
+    [from_env, from_file]
+        .into_iter()
+        .flatten()
+        .find_map(|config| config.lookup(server_address))
+}
+
+fn docker_config_path() -> Option<PathBuf> {
+    let directory = match std::env::var_os("DOCKER_CONFIG") {
+        Some(directory) => PathBuf::from(directory),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".docker"),
+    };
+    Some(directory.join("config.json"))
+}
+
+impl DockerConfig {
+    fn lookup(&self, server_address: &str) -> Option<RegistryAuth> {
+        let host = registry_host(server_address);
+        let helper = self
+            .cred_helpers
+            .iter()
+            .find(|(registry, _)| registry_host(registry) == host)
+            .map(|(_, helper)| helper)
+            .or(self.creds_store.as_ref());
+        if let Some(auth) = helper.and_then(|helper| run_helper(helper, server_address)) {
+            return Some(auth);
+        }
+        let entry = self
+            .auths
+            .iter()
+            .find(|(registry, _)| registry_host(registry) == host)
+            .map(|(_, entry)| entry)?;
+        if let Some(token) = &entry.identitytoken {
+            return Some(RegistryAuth::token(token));
+        }
+        let auth = STANDARD.decode(entry.auth.as_ref()?).ok()?;
+        let auth = String::from_utf8(auth).ok()?;
+        let (username, password) = auth.split_once(':')?;
+        Some(
+            RegistryAuth::builder()
+                .username(username)
+                .password(password)
+                .server_address(server_address)
+                .build(),
+        )
+    }
+}
+
+fn run_helper(helper: &str, server_address: &str) -> Option<RegistryAuth> {
+    let mut child = Command::new(format!("docker-credential-{helper}"))
+        .arg("get")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    child
+        .stdin
+        .take()?
+        .write_all(server_address.as_bytes())
+        .ok()?;
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let credentials: HelperCredentials = serde_json::from_slice(&output.stdout).ok()?;
+    if credentials.username == "<token>" {
+        Some(RegistryAuth::token(credentials.secret))
+    } else {
+        Some(
+            RegistryAuth::builder()
+                .username(credentials.username)
+                .password(credentials.secret)
+                .server_address(server_address)
+                .build(),
+        )
+    }
+}
+
+// the X-Registry-Config header of builds, the credentials of every registry the build may pull
+// from keyed by registry address
+pub(crate) fn registry_config(auths: &HashMap<String, RegistryAuth>) -> String {
+    URL_SAFE.encode(serde_json::to_vec(auths).unwrap_or_default())
+}
+
+// config keys may or may not carry a scheme and the v1 path of the docker hub
+fn registry_host(address: &str) -> &str {
+    let address = address
+        .split_once("://")
+        .map_or(address, |(_scheme, address)| address);
+    address.split('/').next().unwrap_or(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_auth_from_docker_config() {
+        let config: DockerConfig = serde_json::from_str(
+            r#"{"auths": {"https://registry.foo.com/v1/": {"auth": "dXNlcjpzZWNyZXQ="}}}"#,
+        )
+        .unwrap();
+        let auth = config.lookup("registry.foo.com").unwrap();
+        let expected = RegistryAuth::builder()
+            .username("user")
+            .password("secret")
+            .server_address("registry.foo.com")
+            .build();
+        assert_eq!(auth.serialize(), expected.serialize());
+        assert!(config.lookup("registry.bar.com").is_none());
+    }
+
+    #[test]
+    fn can_read_identity_token_from_docker_config() {
+        let config: DockerConfig =
+            serde_json::from_str(r#"{"auths": {"registry.foo.com": {"identitytoken": "token"}}}"#)
+                .unwrap();
+        let auth = config.lookup("https://registry.foo.com").unwrap();
+        assert_eq!(auth.serialize(), RegistryAuth::token("token").serialize());
+    }
+
+    #[test]
+    fn can_encode_registry_config_of_builds() {
+        let auths = HashMap::from([(
+            "registry.foo.com".to_string(),
+            RegistryAuth::builder()
+                .username("user")
+                .password("se?cret~~")
+                .server_address("registry.foo.com")
+                .build(),
+        )]);
+        let header = registry_config(&auths);
+        assert!(!header.contains(['+', '/']));
+        let config: serde_json::Value =
+            serde_json::from_slice(&URL_SAFE.decode(header).unwrap()).unwrap();
+        assert_eq!(
+            config,
+            serde_json::json!({"registry.foo.com": {
+                "username": "user",
+                "password": "se?cret~~",
+                "serveraddress": "registry.foo.com",
+            }})
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    container::{self, GenericContainerBuilder},
+    docker_client::{ExecOptions, ExecResult, ExitResult},
+    error::TestcontainersError,
+    port::{PortError, Protocol},
+};
+use std::{future::Future, ops::RangeInclusive, path::Path, sync::LazyLock};
+use tokio::runtime::{Builder, Runtime};
+
+// a worker thread keeps the background tasks (reaper connection, log streams) running between
+// two blocking calls
+static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+    Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("testcontainers")
+        .enable_all()
+        .build()
+        .expect("🐋 Unable to start the testcontainers runtime")
+});
+
+// runs a future, e.g. a module function, to completion on the internal runtime; it panics when
+// called from within an async context
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME.block_on(future)
+}
+
+// cleanups started by a Drop run here, the runtime of a test ends as soon as the test returns
+pub(crate) fn spawn<F: Future<Output = ()> + Send + 'static>(future: F) {
+    RUNTIME.spawn(future);
+}
+
+pub struct GenericContainer {
+    container: container::GenericContainer,
+}
+
+impl GenericContainer {
+    pub fn create(builder: GenericContainerBuilder) -> Result<Self, TestcontainersError> {
+        let container = block_on(builder.create())?;
+        Ok(GenericContainer { container })
+    }
+
+    pub fn start(self) -> Result<RunningContainer, TestcontainersError> {
+        let container = block_on(self.container.start())?;
+        Ok(RunningContainer { container })
+    }
+
+    pub fn run_to_completion(self) -> Result<ExitResult, TestcontainersError> {
+        block_on(self.container.run_to_completion())
+    }
+
+    pub fn remove(self) -> Result<(), TestcontainersError> {
+        block_on(self.container.remove())
+    }
+
+    pub fn logs(&self) -> Result<String, TestcontainersError> {
+        block_on(self.container.logs())
+    }
+
+    pub fn into_async(self) -> container::GenericContainer {
+        self.container
+    }
+}
+
+pub struct RunningContainer {
+    container: container::RunningContainer,
+}
+
+impl RunningContainer {
+    pub fn stop(self) -> Result<GenericContainer, TestcontainersError> {
+        let container = block_on(self.container.stop())?;
+        Ok(GenericContainer { container })
+    }
+
+    pub fn kill(self) -> Result<GenericContainer, TestcontainersError> {
+        let container = block_on(self.container.kill())?;
+        Ok(GenericContainer { container })
+    }
+
+    pub fn stop_and_remove(self) -> Result<(), TestcontainersError> {
+        block_on(self.container.stop_and_remove())
+    }
+
+    pub fn exec(&self, command_parts: &[&str]) -> Result<ExecResult, TestcontainersError> {
+        block_on(self.container.exec(command_parts))
+    }
+
+    pub fn exec_with(
+        &self,
+        command_parts: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ExecResult, TestcontainersError> {
+        block_on(self.container.exec_with(command_parts, options))
+    }
+
+    pub fn logs(&self) -> Result<String, TestcontainersError> {
+        block_on(self.container.logs())
+    }
+
+    pub fn stdout(&self) -> Result<String, TestcontainersError> {
+        block_on(self.container.stdout())
+    }
+
+    pub fn stderr(&self) -> Result<String, TestcontainersError> {
+        block_on(self.container.stderr())
+    }
+
+    pub fn copy_file_to_container<P: AsRef<Path>>(
+        &self,
+        host_path: P,
+        container_path: &str,
+    ) -> Result<(), TestcontainersError> {
+        block_on(
+            self.container
+                .copy_file_to_container(host_path, container_path),
+        )
+    }
+
+    pub fn copy_string_to_file(
+        &self,
+        contents: &str,
+        container_path: &str,
+    ) -> Result<(), TestcontainersError> {
+        block_on(self.container.copy_string_to_file(contents, container_path))
+    }
+
+    pub fn copy_file_from_container(
+        &self,
+        container_path: &str,
+    ) -> Result<Vec<u8>, TestcontainersError> {
+        block_on(self.container.copy_file_from_container(container_path))
+    }
+
+    pub fn id(&self) -> &str {
+        self.container.id()
+    }
+
+    pub fn name(&self) -> &str {
+        self.container.name()
+    }
+
+    pub fn get_env(&self, key: &str) -> Option<&str> {
+        self.container.get_env(key)
+    }
+
+    pub fn get_host(&self) -> String {
+        self.container.get_host()
+    }
+
+    pub fn get_container_ip(&self, network: &str) -> Option<String> {
+        self.container.get_container_ip(network)
+    }
+
+    pub fn get_host_port_range(
+        &self,
+        ports: RangeInclusive<u16>,
+        protocol: Protocol,
+    ) -> Option<Vec<u16>> {
+        self.container.get_host_port_range(ports, protocol)
+    }
+
+    pub fn get_host_port<S: Into<String>>(&self, container_port_spec: S) -> Option<u16> {
+        self.container.get_host_port(container_port_spec)
+    }
+
+    pub fn try_get_host_port<S: Into<String>>(
+        &self,
+        container_port_spec: S,
+    ) -> Result<u16, PortError> {
+        self.container.try_get_host_port(container_port_spec)
+    }
+
+    pub fn get_first_mapped_port(&self) -> Result<u16, PortError> {
+        self.container.get_first_mapped_port()
+    }
+
+    // e.g. to turn it into a module container type
+    pub fn into_async(self) -> container::RunningContainer {
+        self.container
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn futures_are_run_on_the_internal_runtime() {
+        let handles: Vec<_> = (0..4)
+            .map(|index| {
+                std::thread::spawn(move || {
+                    block_on(async move {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        index
+                    })
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results, vec![0, 1, 2, 3]);
+    }
+}
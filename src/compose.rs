@@ -0,0 +1,193 @@
+use crate::{
+    docker_client::{ContainerClient, DockerClient, RunningState},
+    error::TestcontainersError,
+    reaper,
+    runtime::ContainerRuntime,
+    session,
+    wait::ReadyStrategy,
+};
+use futures_util::future::try_join_all;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    process::Command,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+static NEXT_PROJECT_ID: AtomicU64 = AtomicU64::new(0);
+
+// stacks are run by the docker compose CLI, which has to be installed
+pub struct DockerCompose {
+    files: Vec<PathBuf>,
+    project_name: String,
+    environment_variables: HashMap<String, String>,
+    wait_strategies: HashMap<String, ReadyStrategy>,
+    start_timeout: Duration,
+}
+
+impl DockerCompose {
+    pub fn new<P: Into<PathBuf>>(compose_file: P) -> Self {
+        DockerCompose {
+            files: vec![compose_file.into()],
+            project_name: format!(
+                "testcontainers-{}-{}",
+                session::session_id(),
+                NEXT_PROJECT_ID.fetch_add(1, Ordering::Relaxed)
+            ),
+            environment_variables: HashMap::new(),
+            wait_strategies: HashMap::new(),
+            start_timeout: Duration::from_secs(60),
+        }
+    }
+
+    // later files override earlier ones, like repeated -f options
+    pub fn with_file<P: Into<PathBuf>>(mut self, compose_file: P) -> Self {
+        self.files.push(compose_file.into());
+        self
+    }
+
+    pub fn add_env<S: Into<String>>(mut self, key: S, value: S) -> Self {
+        self.environment_variables.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_wait_strategy<S: Into<String>>(
+        mut self,
+        service: S,
+        wait_strategy: ReadyStrategy,
+    ) -> Self {
+        self.wait_strategies.insert(service.into(), wait_strategy);
+        self
+    }
+
+    pub fn with_start_timeout(mut self, start_timeout: Duration) -> Self {
+        self.start_timeout = start_timeout;
+        self
+    }
+
+    pub async fn start(self) -> Result<RunningDockerCompose, TestcontainersError> {
+        let docker = DockerClient::shared();
+        reaper::register_filter(
+            docker.clone(),
+            &format!("label={PROJECT_LABEL}={}", self.project_name),
+        )
+        .await?;
+        let compose = RunningDockerCompose {
+            files: self.files,
+            project_name: self.project_name,
+            environment_variables: self.environment_variables,
+            docker_host: docker.docker_host().map(String::from),
+            services: HashMap::new(),
+        };
+        compose.run(&["up", "--detach"]).await?;
+        match compose
+            .wait_for_services(&docker, &self.wait_strategies, self.start_timeout)
+            .await
+        {
+            Ok(services) => Ok(RunningDockerCompose {
+                services,
+                ..compose
+            }),
+            Err(error) => {
+                compose.down().await?;
+                Err(error)
+            }
+        }
+    }
+}
+
+pub struct RunningDockerCompose {
+    files: Vec<PathBuf>,
+    project_name: String,
+    environment_variables: HashMap<String, String>,
+    docker_host: Option<String>,
+    services: HashMap<String, (ContainerClient, RunningState)>,
+}
+
+impl RunningDockerCompose {
+    pub fn project_name(&self) -> &str {
+        &self.project_name
+    }
+
+    pub fn get_service_host<S: AsRef<str>>(&self, service: S) -> Option<String> {
+        let (container, _) = self.services.get(service.as_ref())?;
+        Some(container.host())
+    }
+
+    pub fn get_service_host_port<S: AsRef<str>>(&self, service: S, port: u16) -> Option<u16> {
+        let (_, state) = self.services.get(service.as_ref())?;
+        state.ports.get(&format!("{port}/tcp")).copied()
+    }
+
+    pub async fn logs<S: AsRef<str>>(&self, service: S) -> Result<String, TestcontainersError> {
+        match self.services.get(service.as_ref()) {
+            Some((container, _)) => container.logs().await,
+            None => Err(TestcontainersError::Compose(format!(
+                "unknown service {}",
+                service.as_ref()
+            ))),
+        }
+    }
+
+    pub async fn down(self) -> Result<(), TestcontainersError> {
+        self.run(&["down", "--volumes", "--remove-orphans"]).await?;
+        println!("🐋 Compose project {} removed", self.project_name);
+        Ok(())
+    }
+
+    async fn wait_for_services(
+        &self,
+        docker: &DockerClient,
+        wait_strategies: &HashMap<String, ReadyStrategy>,
+        start_timeout: Duration,
+    ) -> Result<HashMap<String, (ContainerClient, RunningState)>, TestcontainersError> {
+        let containers = docker
+            .list_started(PROJECT_LABEL, &self.project_name)
+            .await?;
+        let waits = containers
+            .into_iter()
+            .map(|(labels, container)| async move {
+                let service = labels.get(SERVICE_LABEL).cloned().unwrap_or_default();
+                let wait_strategy = wait_strategies
+                    .get(&service)
+                    .unwrap_or(&ReadyStrategy::None);
+                let state = container
+                    .start_and_wait(wait_strategy, start_timeout, 1)
+                    .await?;
+                Ok::<_, TestcontainersError>((service, (container, state)))
+            });
+        // a scaled service is represented by any of its containers
+        Ok(try_join_all(waits).await?.into_iter().collect())
+    }
+
+    async fn run(&self, args: &[&str]) -> Result<(), TestcontainersError> {
+        let mut command = Command::new("docker");
+        command
+            .arg("compose")
+            .arg("--project-name")
+            .arg(&self.project_name);
+        for file in &self.files {
+            command.arg("--file").arg(file);
+        }
+        command.args(args).envs(&self.environment_variables);
+        if let Some(docker_host) = &self.docker_host {
+            command.env("DOCKER_HOST", docker_host);
+        }
+        let output = tokio::task::spawn_blocking(move || command.output())
+            .await
+            .map_err(|error| TestcontainersError::Compose(error.to_string()))??;
+        if !output.status.success() {
+            return Err(TestcontainersError::Compose(format!(
+                "docker compose {} exited with {}: {}",
+                args.join(" "),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use std::{collections::HashMap, path::PathBuf, sync::OnceLock};
+
+const PROPERTIES_FILE: &str = ".testcontainers.properties";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    docker_host: Option<String>,
+    docker_tls_verify: bool,
+    docker_cert_path: Option<String>,
+    host_override: Option<String>,
+    docker_socket_override: Option<String>,
+    ryuk_disabled: bool,
+    hub_image_name_prefix: Option<String>,
+}
+
+impl Config {
+    pub fn global() -> &'static Config {
+        CONFIG.get_or_init(Config::load)
+    }
+
+    fn load() -> Self {
+        let properties = std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(PROPERTIES_FILE))
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|contents| parse_properties(&contents))
+            .unwrap_or_default();
+        Config::from_sources(&properties, |name| std::env::var(name).ok())
+    }
+
+    // environment variables take precedence over the properties file
+    fn from_sources<F: Fn(&str) -> Option<String>>(
+        properties: &HashMap<String, String>,
+        env: F,
+    ) -> Self {
+        let lookup = |variable: &str, property: &str| {
+            env(variable)
+                .or_else(|| properties.get(property).cloned())
+                .filter(|value| !value.is_empty())
+        };
+        Config {
+            docker_host: lookup("DOCKER_HOST", "docker.host"),
+            docker_tls_verify: lookup("DOCKER_TLS_VERIFY", "docker.tls.verify")
+                .is_some_and(|value| value == "1" || value == "true"),
+            docker_cert_path: lookup("DOCKER_CERT_PATH", "docker.cert.path"),
+            host_override: lookup("TESTCONTAINERS_HOST_OVERRIDE", "tc.host"),
+            docker_socket_override: lookup(
+                "TESTCONTAINERS_DOCKER_SOCKET_OVERRIDE",
+                "docker.socket.override",
+            ),
+            ryuk_disabled: lookup("TESTCONTAINERS_RYUK_DISABLED", "ryuk.disabled")
+                .is_some_and(|value| value == "true"),
+            hub_image_name_prefix: lookup(
+                "TESTCONTAINERS_HUB_IMAGE_NAME_PREFIX",
+                "hub.image.name.prefix",
+            ),
+        }
+    }
+
+    pub fn docker_host(&self) -> Option<&str> {
+        self.docker_host.as_deref()
+    }
+
+    pub fn docker_tls_verify(&self) -> bool {
+        self.docker_tls_verify
+    }
+
+    pub fn docker_cert_path(&self) -> Option<&str> {
+        self.docker_cert_path.as_deref()
+    }
+
+    pub fn host_override(&self) -> Option<&str> {
+        self.host_override.as_deref()
+    }
+
+    // the daemon socket as the daemon sees it, mounted into the reaper
+    pub fn docker_socket_override(&self) -> Option<&str> {
+        self.docker_socket_override.as_deref()
+    }
+
+    pub fn ryuk_disabled(&self) -> bool {
+        self.ryuk_disabled
+    }
+
+    pub fn hub_image_name_prefix(&self) -> Option<&str> {
+        self.hub_image_name_prefix.as_deref()
+    }
+}
+
+fn parse_properties(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| {
+            let separator = line.find(['=', ':'])?;
+            let (key, value) = line.split_at(separator);
+            Some((key.trim().to_string(), value[1..].trim().to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_properties() {
+        let properties = parse_properties(
+            "# comment\n\
+             docker.host=tcp://remote:2375\n\
+             ryuk.disabled : true\n\
+             \n\
+             hub.image.name.prefix=mirror.local/",
+        );
+        assert_eq!(
+            properties,
+            HashMap::from([
+                ("docker.host".to_string(), "tcp://remote:2375".to_string()),
+                ("ryuk.disabled".to_string(), "true".to_string()),
+                (
+                    "hub.image.name.prefix".to_string(),
+                    "mirror.local/".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn environment_overrides_properties() {
+        let properties = parse_properties(
+            "docker.host=tcp://remote:2375\nryuk.disabled=true\ndocker.cert.path=/certs\n\
+             docker.socket.override=/run/docker.sock",
+        );
+        let env = HashMap::from([
+            ("DOCKER_HOST", "unix:///run/docker.sock"),
+            ("DOCKER_TLS_VERIFY", "1"),
+        ]);
+        let config = Config::from_sources(&properties, |name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(config.docker_host(), Some("unix:///run/docker.sock"));
+        assert!(config.docker_tls_verify());
+        assert_eq!(config.docker_cert_path(), Some("/certs"));
+        assert!(config.ryuk_disabled());
+        assert_eq!(config.docker_socket_override(), Some("/run/docker.sock"));
+        assert_eq!(config.hub_image_name_prefix(), None);
+    }
+}
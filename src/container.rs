@@ -1,41 +1,197 @@
 use crate::{
-    docker_client::{ContainerClient, DockerClient},
-    image::DockerImage,
+    auth,
+    definition::ContainerDefinition,
+    docker_client::{
+        ContainerClient, DockerClient, DockerTls, ExecOptions, ExecOutput, ExecResult, ExitResult,
+        LogOptions, RunningState,
+    },
+    error::TestcontainersError,
+    image::{self, BuildImageInstructions, DockerImage, ImageArchive, PullPolicy, PullRetry},
+    mount::{BindMode, BindMount},
+    network::Network,
+    port::{ContainerPort, PortError, Protocol},
+    reaper,
+    runtime::{self, ContainerRuntime, EngineCreateOpts, Gpus, Ulimit},
+    session::{Session, SESSION_HOST_LABEL, SESSION_ID_LABEL, SESSION_PID_LABEL},
+    volume::Volume,
+    wait::WaitStrategy,
 };
-use docker_api::opts::{ContainerCreateOpts, HostPort, PublishPort};
+use docker_api::{
+    models::{ContainerInspect200Response, EndpointIpamConfig, EndpointSettings, NetworkingConfig},
+    opts::{ContainerCreateOpts, HostPort, RegistryAuth},
+};
+use futures_util::stream::BoxStream;
+use hyper::StatusCode;
 use log::info;
-use regex::Regex;
 use std::{
-    collections::{HashMap, HashSet},
-    time::{Duration, Instant},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
 };
 
+pub use crate::wait::ReadyStrategy;
+
+const REUSE_HASH_LABEL: &str = "org.testcontainers.hash";
+
+static NEXT_BUILDER_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone)]
+pub(crate) struct Dependency {
+    pub(crate) id: u64,
+    pub(crate) condition: Option<ReadyStrategy>,
+}
+
+#[derive(Clone, Hash)]
+enum FileSource {
+    HostPath(PathBuf),
+    Contents(Vec<u8>),
+}
+
+impl FileSource {
+    fn read(&self) -> Result<Vec<u8>, TestcontainersError> {
+        match self {
+            FileSource::HostPath(path) => Ok(std::fs::read(path)?),
+            FileSource::Contents(contents) => Ok(contents.clone()),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct GenericContainerBuilder {
+    pub(crate) id: u64,
+    pub(crate) dependencies: Vec<Dependency>,
     image: DockerImage,
     environment_variables: HashMap<String, String>,
     exposed_ports: HashMap<String, Option<u16>>,
     volumes: HashSet<String>,
+    volumes_from: Vec<String>,
     labels: HashMap<String, String>,
     command: Option<Vec<String>>,
+    entrypoint: Option<Vec<String>>,
+    extra_hosts: Vec<String>,
     wait_strategy_on_startup: ReadyStrategy,
     start_timeout: Duration,
+    consecutive_successes: u32,
+    reuse: bool,
+    auto_remove: bool,
+    privileged: bool,
+    network: Option<String>,
+    network_aliases: Vec<String>,
+    ipv4_address: Option<String>,
+    ipv6_address: Option<String>,
+    mac_address: Option<String>,
+    network_mode: Option<String>,
+    container_name: Option<String>,
+    hostname: Option<String>,
+    domainname: Option<String>,
+    workdir: Option<String>,
+    user: Option<String>,
+    tmpfs: HashMap<String, String>,
+    ulimits: Vec<Ulimit>,
+    dns: Vec<String>,
+    dns_search: Vec<String>,
+    dns_options: Vec<String>,
+    pid_mode: Option<String>,
+    ipc_mode: Option<String>,
+    uts_mode: Option<String>,
+    init: bool,
+    oom_kill_disable: bool,
+    oom_score_adj: Option<i32>,
+    group_add: Vec<String>,
+    files_to_copy: Vec<(FileSource, String)>,
+    docker_host: Option<String>,
+    docker_tls: Option<DockerTls>,
+    runtime: Option<Arc<dyn ContainerRuntime>>,
+    pull_policy: PullPolicy,
+    registry_auth: Option<RegistryAuth>,
+    pull_retry: PullRetry,
+    platform: Option<String>,
+    gpus: Option<Gpus>,
+    devices: Vec<HashMap<String, String>>,
+    oci_runtime: Option<String>,
+    security_opts: Vec<String>,
+    stop_signal: Option<String>,
+    stop_timeout: Option<Duration>,
+    device_cgroup_rules: Vec<String>,
+    image_archive: Option<ImageArchive>,
+    configuration_errors: Vec<String>,
 }
 
 impl GenericContainerBuilder {
     fn new(image: DockerImage) -> Self {
         GenericContainerBuilder {
+            id: NEXT_BUILDER_ID.fetch_add(1, Ordering::Relaxed),
+            dependencies: vec![],
             image,
             environment_variables: HashMap::new(),
             exposed_ports: HashMap::new(),
             volumes: HashSet::new(),
+            volumes_from: vec![],
             labels: HashMap::new(),
             command: None,
+            entrypoint: None,
+            extra_hosts: vec![],
             wait_strategy_on_startup: ReadyStrategy::None,
             start_timeout: Duration::from_secs(30),
+            consecutive_successes: 1,
+            reuse: false,
+            auto_remove: false,
+            privileged: false,
+            network: None,
+            network_aliases: vec![],
+            ipv4_address: None,
+            ipv6_address: None,
+            mac_address: None,
+            network_mode: None,
+            container_name: None,
+            hostname: None,
+            domainname: None,
+            workdir: None,
+            user: None,
+            tmpfs: HashMap::new(),
+            ulimits: vec![],
+            dns: vec![],
+            dns_search: vec![],
+            dns_options: vec![],
+            pid_mode: None,
+            ipc_mode: None,
+            uts_mode: None,
+            init: false,
+            oom_kill_disable: false,
+            oom_score_adj: None,
+            group_add: vec![],
+            files_to_copy: vec![],
+            docker_host: None,
+            docker_tls: None,
+            runtime: None,
+            pull_policy: PullPolicy::IfNotPresent,
+            registry_auth: None,
+            pull_retry: PullRetry::default(),
+            platform: None,
+            gpus: None,
+            devices: vec![],
+            oci_runtime: None,
+            security_opts: vec![],
+            stop_signal: None,
+            stop_timeout: None,
+            device_cgroup_rules: vec![],
+            image_archive: None,
+            configuration_errors: vec![],
         }
     }
 
+    // a testcontainers.toml, or the same definition as yaml or json, see ContainerDefinition
+    pub fn from_file<P: AsRef<Path>>(definition_file: P) -> Result<Self, TestcontainersError> {
+        ContainerDefinition::from_file(definition_file)?.into_builder()
+    }
+
     pub fn add_env<S: Into<String>>(mut self, key: S, value: S) -> Self {
         self.environment_variables.insert(key.into(), value.into());
         self
@@ -52,54 +208,783 @@ impl GenericContainerBuilder {
     }
 
     pub fn add_exposed_tcp_port(self, port: u16) -> Self {
-        self.add_exposed_port(None, format!("{port}/tcp"))
+        self.add_exposed_port(None, ContainerPort::tcp(port))
+    }
+
+    pub fn add_exposed_udp_port(self, port: u16) -> Self {
+        self.add_exposed_port(None, ContainerPort::udp(port))
+    }
+
+    pub fn add_exposed_sctp_port(self, port: u16) -> Self {
+        self.add_exposed_port(None, ContainerPort::sctp(port))
+    }
+
+    // each port of the range gets its own random host port, see get_host_port_range
+    pub fn add_exposed_port_range(self, ports: RangeInclusive<u16>, protocol: Protocol) -> Self {
+        ports.fold(self, |builder, number| {
+            builder.add_exposed_port(None, ContainerPort { number, protocol })
+        })
     }
 
     pub fn add_fixed_exposed_tcp_port(self, host_port: u16, container_port: u16) -> Self {
-        self.add_exposed_port(Some(host_port), format!("{container_port}/tcp"))
+        self.add_exposed_port(Some(host_port), ContainerPort::tcp(container_port))
     }
 
+    // raw docker -v syntax, with_bind_mount and with_mount are the typed alternatives
     pub fn add_volume<S: Into<String>>(mut self, volume: S) -> Self {
         self.volumes.insert(volume.into());
         self
     }
 
+    pub fn with_bind_mount<P: Into<PathBuf>, S: Into<String>>(
+        self,
+        host_path: P,
+        container_path: S,
+        mode: BindMode,
+    ) -> Self {
+        self.with_mount(BindMount::new(host_path, container_path, mode))
+    }
+
+    pub fn with_volume_mount<S: Into<String>>(self, volume: &Volume, container_path: S) -> Self {
+        let volume = format!("{}:{}", volume.name(), container_path.into());
+        self.add_volume(volume)
+    }
+
+    // every volume of the other container is mounted at the same path, e.g. data seeded by it
+    pub fn with_volumes_from(mut self, container: &RunningContainer) -> Self {
+        self.volumes_from.push(container.id().to_string());
+        self
+    }
+
+    pub fn with_mount(mut self, mount: BindMount) -> Self {
+        match mount.to_bind() {
+            Ok(bind) => {
+                self.volumes.insert(bind);
+            }
+            Err(error) => self.configuration_errors.push(format!(
+                "invalid bind mount {}: {error}",
+                mount.host_path().display()
+            )),
+        }
+        self
+    }
+
     pub fn add_label<S: Into<String>>(mut self, name: S, value: S) -> Self {
         self.labels.insert(name.into(), value.into());
         self
     }
 
+    pub fn copy_file_to_container<P: Into<PathBuf>, S: Into<String>>(
+        mut self,
+        host_path: P,
+        container_path: S,
+    ) -> Self {
+        self.files_to_copy.push((
+            FileSource::HostPath(host_path.into()),
+            container_path.into(),
+        ));
+        self
+    }
+
+    // the directory is walked right away, files created afterwards are not copied
+    pub fn copy_dir_to_container<P: AsRef<Path>, S: Into<String>>(
+        mut self,
+        host_dir: P,
+        container_dir: S,
+    ) -> Self {
+        let host_dir = host_dir.as_ref();
+        let container_dir: String = container_dir.into();
+        match files_in(host_dir) {
+            Ok(files) => {
+                for file in files {
+                    let relative_path = file.strip_prefix(host_dir).unwrap_or(&file);
+                    let container_path = format!(
+                        "{}/{}",
+                        container_dir.trim_end_matches('/'),
+                        relative_path.to_string_lossy()
+                    );
+                    self.files_to_copy
+                        .push((FileSource::HostPath(file), container_path));
+                }
+            }
+            Err(error) => self.configuration_errors.push(format!(
+                "unable to read directory {}: {error}",
+                host_dir.display()
+            )),
+        }
+        self
+    }
+
+    pub fn with_copy_string_to_file<C: Into<String>, S: Into<String>>(
+        mut self,
+        contents: C,
+        container_path: S,
+    ) -> Self {
+        let contents: String = contents.into();
+        self.files_to_copy.push((
+            FileSource::Contents(contents.into_bytes()),
+            container_path.into(),
+        ));
+        self
+    }
+
     pub fn with_command(mut self, command_parts: &[&str]) -> Self {
         self.command = Some(command_parts.iter().map(|s| s.to_string()).collect());
         self
     }
 
-    pub fn wait_for_log_on_startup<S: Into<String>>(mut self, log_regex: S) -> Self {
+    pub fn with_entrypoint(mut self, entrypoint_parts: &[&str]) -> Self {
+        self.entrypoint = Some(entrypoint_parts.iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    // an /etc/hosts entry, the ip can be host-gateway to reach the docker host
+    pub fn with_extra_host<S: Into<String>>(mut self, hostname: S, ip: S) -> Self {
+        self.extra_hosts
+            .push(format!("{}:{}", hostname.into(), ip.into()));
+        self
+    }
+
+    pub fn wait_for_log_on_startup<S: Into<String>>(self, log_regex: S) -> Self {
+        self.wait_for_log_on_startup_with(log_regex, LogOptions::default())
+    }
+
+    pub fn wait_for_log_on_startup_with<S: Into<String>>(
+        mut self,
+        log_regex: S,
+        options: LogOptions,
+    ) -> Self {
         let regex: String = log_regex.into();
-        let regex = regex
-            .parse()
-            .expect(format!("a valid regular expression but it was {regex}").as_str());
-        self.wait_strategy_on_startup = ReadyStrategy::LogMessageRegExp(regex);
+        match regex.parse() {
+            Ok(regex) => {
+                self.wait_strategy_on_startup = ReadyStrategy::LogMessageRegExp(regex, options)
+            }
+            Err(error) => self
+                .configuration_errors
+                .push(format!("invalid log regular expression {regex}: {error}")),
+        }
+        self
+    }
+
+    pub fn wait_for_port_listening(mut self, port: u16) -> Self {
+        self.wait_strategy_on_startup = ReadyStrategy::PortListening(format!("{port}/tcp"));
+        self
+    }
+
+    pub fn wait_for_command_on_startup(mut self, command_parts: &[&str]) -> Self {
+        self.wait_strategy_on_startup =
+            ReadyStrategy::ExecCommand(command_parts.iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    pub fn wait_for_file_on_startup<S: Into<String>>(mut self, path: S) -> Self {
+        self.wait_strategy_on_startup = ReadyStrategy::FileExists(path.into());
+        self
+    }
+
+    pub fn wait_for_exit(mut self) -> Self {
+        self.wait_strategy_on_startup = ReadyStrategy::Exited;
+        self
+    }
+
+    pub fn wait_for<W: WaitStrategy + 'static>(mut self, strategy: W) -> Self {
+        self.wait_strategy_on_startup = ReadyStrategy::Custom(Arc::new(strategy));
+        self
+    }
+
+    pub fn wait_for_healthy(mut self) -> Self {
+        self.wait_strategy_on_startup = ReadyStrategy::StateHealthy;
+        self
+    }
+
+    pub fn with_wait_strategy_on_startup(mut self, strategy: ReadyStrategy) -> Self {
+        self.wait_strategy_on_startup = strategy;
+        self
+    }
+
+    pub fn with_consecutive_successes(mut self, count: u32) -> Self {
+        self.consecutive_successes = count.max(1);
         self
     }
 
     pub fn with_start_timeout(mut self, duration_expression: &str) -> Self {
-        let duration = parse_duration::parse(duration_expression)
-            .expect(format!("a parseable duration but it was {duration_expression}").as_str());
-        self.start_timeout = duration;
+        match parse_duration::parse(duration_expression) {
+            Ok(duration) => self.start_timeout = duration,
+            Err(error) => self.configuration_errors.push(format!(
+                "invalid start timeout {duration_expression}: {error}"
+            )),
+        }
+        self
+    }
+
+    // a name already taken gets a random suffix, see GenericContainer::name for the actual one
+    pub fn with_container_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.container_name = Some(name.into());
+        self
+    }
+
+    pub fn with_hostname<S: Into<String>>(mut self, hostname: S) -> Self {
+        self.hostname = Some(hostname.into());
+        self
+    }
+
+    pub fn with_domainname<S: Into<String>>(mut self, domainname: S) -> Self {
+        self.domainname = Some(domainname.into());
+        self
+    }
+
+    pub fn with_workdir<S: Into<String>>(mut self, workdir: S) -> Self {
+        self.workdir = Some(workdir.into());
+        self
+    }
+
+    // "uid", "uid:gid" or a user name known by the image, files written to bind mounts then
+    // belong to that user instead of root
+    pub fn with_user<S: Into<String>>(mut self, user: S) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    // options like "rw,size=256m" are passed to mount, e.g. to keep a database on tmpfs
+    pub fn with_tmpfs<S: Into<String>>(mut self, container_path: S, options: S) -> Self {
+        self.tmpfs.insert(container_path.into(), options.into());
+        self
+    }
+
+    // e.g. nofile or memlock, -1 stands for unlimited
+    pub fn with_ulimit<S: Into<String>>(mut self, name: S, soft: i64, hard: i64) -> Self {
+        let name: String = name.into();
+        self.ulimits.retain(|ulimit| ulimit.name != name);
+        self.ulimits.push(Ulimit { name, soft, hard });
+        self
+    }
+
+    // resolvers are tried in the order they are added
+    pub fn with_dns<S: Into<String>>(mut self, ip: S) -> Self {
+        self.dns.push(ip.into());
+        self
+    }
+
+    pub fn with_dns_search<S: Into<String>>(mut self, domain: S) -> Self {
+        self.dns_search.push(domain.into());
+        self
+    }
+
+    // resolv.conf options, e.g. ndots:2
+    pub fn with_dns_option<S: Into<String>>(mut self, option: S) -> Self {
+        self.dns_options.push(option.into());
+        self
+    }
+
+    // namespaces are shared with "host" or another container with "container:<id>"
+    pub fn with_pid_mode<S: Into<String>>(mut self, mode: S) -> Self {
+        self.pid_mode = Some(mode.into());
+        self
+    }
+
+    // also "none", "private" or "shareable"
+    pub fn with_ipc_mode<S: Into<String>>(mut self, mode: S) -> Self {
+        self.ipc_mode = Some(mode.into());
+        self
+    }
+
+    pub fn with_uts_mode<S: Into<String>>(mut self, mode: S) -> Self {
+        self.uts_mode = Some(mode.into());
+        self
+    }
+
+    // used by stop(), kill() still sends SIGKILL
+    pub fn with_stop_signal<S: Into<String>>(mut self, signal: S) -> Self {
+        self.stop_signal = Some(signal.into());
+        self
+    }
+
+    // how long stop() waits for the container to exit before killing it, docker counts seconds
+    pub fn with_stop_timeout(mut self, timeout: Duration) -> Self {
+        self.stop_timeout = Some(timeout);
+        self
+    }
+
+    // runs an init process as PID 1 which forwards signals and reaps zombies
+    pub fn with_init(mut self, init: bool) -> Self {
+        self.init = init;
+        self
+    }
+
+    // only effective with a memory limit, on cgroup v1 hosts
+    pub fn with_oom_kill_disable(mut self, oom_kill_disable: bool) -> Self {
+        self.oom_kill_disable = oom_kill_disable;
+        self
+    }
+
+    // from -1000 (never killed) to 1000 (killed first)
+    pub fn with_oom_score_adj(mut self, oom_score_adj: i32) -> Self {
+        self.oom_score_adj = Some(oom_score_adj);
+        self
+    }
+
+    // a supplementary group of the container user, by name or GID, e.g. the GID owning the
+    // mounted docker socket
+    pub fn with_group_add<S: Into<String>>(mut self, group: S) -> Self {
+        self.group_add.push(group.into());
+        self
+    }
+
+    pub fn with_network(mut self, network: &Network) -> Self {
+        self.network = Some(network.name().to_string());
+        self
+    }
+
+    pub fn with_network_alias<S: Into<String>>(mut self, alias: S) -> Self {
+        self.network_aliases.push(alias.into());
+        self
+    }
+
+    // static addresses on the network given to with_network, which needs user defined subnets,
+    // see Network::create_with_subnets
+    pub fn with_ipv4_address<S: Into<String>>(mut self, ip: S) -> Self {
+        self.ipv4_address = Some(ip.into());
+        self
+    }
+
+    pub fn with_ipv6_address<S: Into<String>>(mut self, ip: S) -> Self {
+        self.ipv6_address = Some(ip.into());
+        self
+    }
+
+    pub fn with_mac_address<S: Into<String>>(mut self, mac: S) -> Self {
+        self.mac_address = Some(mac.into());
+        self
+    }
+
+    pub fn with_network_mode<S: Into<String>>(mut self, network_mode: S) -> Self {
+        self.network_mode = Some(network_mode.into());
+        self
+    }
+
+    pub fn depends_on(mut self, other: &GenericContainerBuilder) -> Self {
+        self.dependencies.push(Dependency {
+            id: other.id,
+            condition: None,
+        });
+        self
+    }
+
+    pub fn depends_on_with(
+        mut self,
+        other: &GenericContainerBuilder,
+        condition: ReadyStrategy,
+    ) -> Self {
+        self.dependencies.push(Dependency {
+            id: other.id,
+            condition: Some(condition),
+        });
+        self
+    }
+
+    pub fn with_docker_host<S: Into<String>>(mut self, docker_host: S) -> Self {
+        self.docker_host = Some(docker_host.into());
+        self
+    }
+
+    pub fn with_docker_tls(mut self, docker_tls: DockerTls) -> Self {
+        self.docker_tls = Some(docker_tls);
+        self
+    }
+
+    pub fn with_runtime(mut self, runtime: Arc<dyn ContainerRuntime>) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    pub fn with_pull_policy(mut self, pull_policy: PullPolicy) -> Self {
+        self.pull_policy = pull_policy;
+        self
+    }
+
+    pub fn with_pull_retry(mut self, pull_retry: PullRetry) -> Self {
+        self.pull_retry = pull_retry;
+        self
+    }
+
+    pub fn with_dockerfile_name<S: Into<String>>(self, dockerfile: S) -> Self {
+        self.update_build("dockerfile name", |build| {
+            build.dockerfile = Some(dockerfile.into())
+        })
+    }
+
+    pub fn with_build_context_file<S: Into<String>, C: Into<Vec<u8>>>(
+        self,
+        path: S,
+        contents: C,
+    ) -> Self {
+        self.update_build("build context file", |build| {
+            build.context_files.insert(path.into(), contents.into());
+        })
+    }
+
+    pub fn with_build_arg<S: Into<String>>(self, key: S, value: S) -> Self {
+        self.update_build("build arg", |build| {
+            build.build_args.insert(key.into(), value.into());
+        })
+    }
+
+    pub fn with_build_target<S: Into<String>>(self, target: S) -> Self {
+        self.update_build("build target", |build| build.target = Some(target.into()))
+    }
+
+    pub fn with_build_label<S: Into<String>>(self, key: S, value: S) -> Self {
+        self.update_build("build label", |build| {
+            build.labels.insert(key.into(), value.into());
+        })
+    }
+
+    pub fn with_build_no_cache(self, no_cache: bool) -> Self {
+        self.update_build("build no-cache", |build| build.no_cache = no_cache)
+    }
+
+    pub fn with_build_pull(self, pull: bool) -> Self {
+        self.update_build("build pull", |build| build.pull = pull)
+    }
+
+    fn update_build<F: FnOnce(&mut BuildImageInstructions)>(
+        mut self,
+        option: &str,
+        update: F,
+    ) -> Self {
+        match self.image.build_instructions().cloned() {
+            Some(mut build) => {
+                update(&mut build);
+                self.image = DockerImage::from_build(build);
+            }
+            None => self.configuration_errors.push(format!(
+                "{option} requires an image built from a dockerfile"
+            )),
+        }
+        self
+    }
+
+    // e.g. linux/amd64, the image is then pulled and run for that platform, emulated if needed
+    pub fn with_platform<S: Into<String>>(mut self, platform: S) -> Self {
+        self.platform = Some(platform.into());
+        self
+    }
+
+    // like docker run --gpus, which requires the nvidia container toolkit on the docker host
+    pub fn with_gpus(mut self, gpus: Gpus) -> Self {
+        self.gpus = Some(gpus);
+        self
+    }
+
+    // e.g. /dev/fuse with rwm permissions
+    pub fn with_device<S: Into<String>>(
+        mut self,
+        host_path: S,
+        container_path: S,
+        cgroup_permissions: S,
+    ) -> Self {
+        self.devices.push(HashMap::from([
+            ("PathOnHost".to_string(), host_path.into()),
+            ("PathInContainer".to_string(), container_path.into()),
+            ("CgroupPermissions".to_string(), cgroup_permissions.into()),
+        ]));
+        self
+    }
+
+    // e.g. "c 189:* rmw" to access devices plugged after the container is started
+    pub fn with_device_cgroup_rule<S: Into<String>>(mut self, rule: S) -> Self {
+        self.device_cgroup_rules.push(rule.into());
+        self
+    }
+
+    // the low level runtime registered in the docker daemon, e.g. nvidia, runsc for gVisor or kata
+    pub fn with_oci_runtime<S: Into<String>>(mut self, runtime: S) -> Self {
+        self.oci_runtime = Some(runtime.into());
         self
     }
 
-    pub async fn create(self) -> Result<GenericContainer, docker_api::Error> {
-        let docker = DockerClient::default();
-        if let Some(build_opts) = self.image.clone().into() {
+    // e.g. seccomp=unconfined for browsers, apparmor=<profile> or no-new-privileges
+    pub fn with_security_opt<S: Into<String>>(mut self, security_opt: S) -> Self {
+        self.security_opts.push(security_opt.into());
+        self
+    }
+
+    // loaded into the daemon before the pull policy looks for the image locally
+    pub fn with_image_archive(mut self, image_archive: ImageArchive) -> Self {
+        self.image_archive = Some(image_archive);
+        self
+    }
+
+    pub fn with_registry_auth<S: Into<String>>(mut self, username: S, password: S) -> Self {
+        self.registry_auth = Some(
+            RegistryAuth::builder()
+                .username(username)
+                .password(password)
+                .server_address(self.image.registry_address())
+                .build(),
+        );
+        self
+    }
+
+    pub fn with_auto_remove(mut self, auto_remove: bool) -> Self {
+        self.auto_remove = auto_remove;
+        self
+    }
+
+    pub fn with_privileged(mut self, privileged: bool) -> Self {
+        self.privileged = privileged;
+        self
+    }
+
+    pub fn with_reuse(mut self, reuse: bool) -> Self {
+        self.reuse = reuse;
+        self
+    }
+
+    pub async fn create(self) -> Result<GenericContainer, TestcontainersError> {
+        if !self.configuration_errors.is_empty() {
+            return Err(TestcontainersError::InvalidConfiguration(
+                self.configuration_errors.join(", "),
+            ));
+        }
+        let has_static_address = self.ipv4_address.is_some()
+            || self.ipv6_address.is_some()
+            || self.mac_address.is_some();
+        if has_static_address && self.network.is_none() {
+            return Err(TestcontainersError::InvalidConfiguration(
+                "static addresses require a network given to with_network".to_string(),
+            ));
+        }
+        if self.reuse {
+            return self.create_or_reuse().await;
+        }
+        reaper::ensure_started(self.runtime()?).await?;
+        let mut builder = self;
+        builder.labels.extend(Session::current().labels());
+        builder.create_without_reaper().await
+    }
+
+    async fn create_or_reuse(self) -> Result<GenericContainer, TestcontainersError> {
+        let hash = self.configuration_hash();
+        let docker = self.runtime()?;
+        if let Some(container) = docker.find_running(REUSE_HASH_LABEL, &hash).await? {
+            info!("🐋 Reusing running container for image {}", self.image);
+            return Ok(GenericContainer {
+                params: self,
+                container,
+            });
+        }
+        // reusable containers are not attached to the session so that the reaper keeps them
+        self.add_label(REUSE_HASH_LABEL, &hash)
+            .create_without_reaper()
+            .await
+    }
+
+    async fn should_pull(
+        &self,
+        docker: &dyn ContainerRuntime,
+    ) -> Result<bool, TestcontainersError> {
+        match self.pull_policy {
+            PullPolicy::Always => Ok(true),
+            PullPolicy::IfNotPresent if !docker.image_exists_locally(&self.image).await? => {
+                Ok(true)
+            }
+            PullPolicy::IfNotPresent => match &self.platform {
+                Some(platform) => Ok(!docker
+                    .image_platform(&self.image)
+                    .await?
+                    .is_some_and(|local_platform| platform_matches(platform, &local_platform))),
+                None => Ok(false),
+            },
+            PullPolicy::Never if docker.image_exists_locally(&self.image).await? => Ok(false),
+            PullPolicy::Never => Err(TestcontainersError::ImageNotFound {
+                image: self.image.to_string(),
+            }),
+        }
+    }
+
+    fn runtime(&self) -> Result<Arc<dyn ContainerRuntime>, TestcontainersError> {
+        match &self.runtime {
+            Some(runtime) => Ok(runtime.clone()),
+            None if self.docker_host.is_none() && self.docker_tls.is_none() => {
+                Ok(DockerClient::shared())
+            }
+            None => Ok(Arc::new(DockerClient::connect(
+                self.docker_host.as_deref(),
+                self.docker_tls.as_ref(),
+            )?)),
+        }
+    }
+
+    // DefaultHasher is stable across runs as long as the toolchain doesn't change
+    fn configuration_hash(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.image.to_string().hash(&mut hasher);
+        let mut environment_variables: Vec<_> = self.environment_variables.iter().collect();
+        environment_variables.sort();
+        environment_variables.hash(&mut hasher);
+        let mut exposed_ports: Vec<_> = self.exposed_ports.iter().collect();
+        exposed_ports.sort();
+        exposed_ports.hash(&mut hasher);
+        let mut volumes: Vec<_> = self.volumes.iter().collect();
+        volumes.sort();
+        volumes.hash(&mut hasher);
+        self.volumes_from.hash(&mut hasher);
+        let mut labels: Vec<_> = self.labels.iter().collect();
+        labels.sort();
+        labels.hash(&mut hasher);
+        self.command.hash(&mut hasher);
+        self.entrypoint.hash(&mut hasher);
+        self.extra_hosts.hash(&mut hasher);
+        self.privileged.hash(&mut hasher);
+        self.network.hash(&mut hasher);
+        self.network_aliases.hash(&mut hasher);
+        self.ipv4_address.hash(&mut hasher);
+        self.ipv6_address.hash(&mut hasher);
+        self.mac_address.hash(&mut hasher);
+        self.network_mode.hash(&mut hasher);
+        self.container_name.hash(&mut hasher);
+        self.hostname.hash(&mut hasher);
+        self.domainname.hash(&mut hasher);
+        self.workdir.hash(&mut hasher);
+        self.user.hash(&mut hasher);
+        let mut tmpfs: Vec<_> = self.tmpfs.iter().collect();
+        tmpfs.sort();
+        tmpfs.hash(&mut hasher);
+        let mut ulimits: Vec<_> = self.ulimits.iter().collect();
+        ulimits.sort_by(|first, second| first.name.cmp(&second.name));
+        ulimits.hash(&mut hasher);
+        self.dns.hash(&mut hasher);
+        self.dns_search.hash(&mut hasher);
+        self.dns_options.hash(&mut hasher);
+        self.pid_mode.hash(&mut hasher);
+        self.ipc_mode.hash(&mut hasher);
+        self.uts_mode.hash(&mut hasher);
+        self.init.hash(&mut hasher);
+        self.oom_kill_disable.hash(&mut hasher);
+        self.oom_score_adj.hash(&mut hasher);
+        self.group_add.hash(&mut hasher);
+        self.files_to_copy.hash(&mut hasher);
+        self.platform.hash(&mut hasher);
+        self.gpus.hash(&mut hasher);
+        let devices: Vec<Vec<_>> = self
+            .devices
+            .iter()
+            .map(|device| {
+                let mut device: Vec<_> = device.iter().collect();
+                device.sort();
+                device
+            })
+            .collect();
+        devices.hash(&mut hasher);
+        self.device_cgroup_rules.hash(&mut hasher);
+        self.oci_runtime.hash(&mut hasher);
+        self.security_opts.hash(&mut hasher);
+        self.stop_signal.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    async fn create_container(
+        &self,
+        docker: &dyn ContainerRuntime,
+    ) -> Result<ContainerClient, docker_api::Error> {
+        docker
+            .create(
+                &self.clone().into(),
+                &EngineCreateOpts {
+                    name: self.container_name.clone(),
+                    platform: self.platform.clone(),
+                    gpus: self.gpus.clone(),
+                    device_cgroup_rules: self.device_cgroup_rules.clone(),
+                    tmpfs: self.tmpfs.clone(),
+                    ulimits: self.ulimits.clone(),
+                    dns: self.dns.clone(),
+                    dns_search: self.dns_search.clone(),
+                    dns_options: self.dns_options.clone(),
+                    pid_mode: self.pid_mode.clone(),
+                    ipc_mode: self.ipc_mode.clone(),
+                    uts_mode: self.uts_mode.clone(),
+                    init: self.init,
+                    oom_kill_disable: self.oom_kill_disable,
+                    oom_score_adj: self.oom_score_adj,
+                    group_add: self.group_add.clone(),
+                },
+            )
+            .await
+    }
+
+    pub(crate) async fn create_without_reaper(
+        mut self,
+    ) -> Result<GenericContainer, TestcontainersError> {
+        let docker = self.runtime()?;
+        if let Some(archive) = &self.image_archive {
+            info!("🐋 Loading image archive {archive}");
+            runtime::load_once(docker.as_ref(), archive)
+                .await
+                .map_err(|source| TestcontainersError::ImageLoad {
+                    archive: archive.to_string(),
+                    source,
+                })?;
+        }
+        if let Some(build) = self.image.build_instructions() {
             info!("🐋 Building image {}", self.image);
-            docker.build(&build_opts).await?;
-        } else if !docker.image_exists_locally(&self.image).await? {
+            let mut auths = HashMap::new();
+            for registry in build.base_image_registries() {
+                if let Some(auth) = auth::lookup(&registry).await {
+                    auths.insert(registry, auth);
+                }
+            }
+            // the credentials given to the builder win over the docker config
+            if let Some(auth) = &self.registry_auth {
+                auths.insert(self.image.registry_address().to_string(), auth.clone());
+            }
+            docker
+                .build(&self.image, self.platform.as_deref(), &auths)
+                .await
+                .map_err(|source| TestcontainersError::ImageBuild {
+                    image: self.image.to_string(),
+                    source,
+                })?;
+        } else if self.should_pull(docker.as_ref()).await? {
             info!("🐋 Pulling image {}", self.image);
-            docker.pull(&self.image).await?
+            let auth = match &self.registry_auth {
+                Some(auth) => Some(auth.clone()),
+                None => auth::lookup(self.image.registry_address()).await,
+            };
+            runtime::pull_once(
+                docker.clone(),
+                &self.image,
+                self.platform.clone(),
+                auth,
+                self.pull_retry,
+            )
+            .await
+            .map_err(|source| TestcontainersError::ImagePull {
+                image: self.image.to_string(),
+                source,
+            })?;
+        }
+        let container = match self.create_container(docker.as_ref()).await {
+            Err(docker_api::Error::Fault { code, .. })
+                if code == StatusCode::CONFLICT && self.container_name.is_some() =>
+            {
+                let name = self.container_name.clone().unwrap_or_default();
+                let unique_name = format!("{name}-{}", unique_suffix());
+                info!("🐋 Container name {name} is already in use, using {unique_name}");
+                self.container_name = Some(unique_name);
+                self.create_container(docker.as_ref()).await
+            }
+            result => result,
+        }
+        .map_err(|source| TestcontainersError::ContainerCreate {
+            image: self.image.to_string(),
+            source,
+        })?;
+        for (source, container_path) in &self.files_to_copy {
+            container
+                .copy_file_into(container_path, &source.read()?)
+                .await?;
         }
-        let container = docker.create(&self.clone().into()).await?;
         Ok(GenericContainer {
             params: self,
             container,
@@ -107,24 +992,157 @@ impl GenericContainerBuilder {
     }
 }
 
-impl Into<ContainerCreateOpts> for GenericContainerBuilder {
-    fn into(self) -> ContainerCreateOpts {
+fn unique_suffix() -> String {
+    let mut hasher = DefaultHasher::new();
+    NEXT_BUILDER_ID
+        .fetch_add(1, Ordering::Relaxed)
+        .hash(&mut hasher);
+    SystemTime::now().hash(&mut hasher);
+    format!("{:08x}", hasher.finish() as u32)
+}
+
+fn files_in(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_in(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// the image outlives the session so it must not carry the labels used by the reaper and reuse
+async fn commit(
+    container: &ContainerClient,
+    tag: &str,
+) -> Result<DockerImage, TestcontainersError> {
+    let image: DockerImage = tag.parse().map_err(|error| {
+        TestcontainersError::InvalidConfiguration(format!("invalid image tag {tag}: {error}"))
+    })?;
+    if image.get_full_name().contains('@') {
+        return Err(TestcontainersError::InvalidConfiguration(format!(
+            "invalid image tag {tag}: a digest can't be committed to"
+        )));
+    }
+    container
+        .commit(
+            &image.qualified_repository(),
+            image.tag(),
+            &[
+                SESSION_ID_LABEL,
+                SESSION_PID_LABEL,
+                SESSION_HOST_LABEL,
+                REUSE_HASH_LABEL,
+            ],
+        )
+        .await?;
+    info!("🐋 Container {} committed to image {image}", container.id());
+    Ok(image)
+}
+
+// linux/arm64 matches linux/arm64/v8, the variant is only compared when both specify one
+fn platform_matches(requested: &str, actual: &str) -> bool {
+    requested
+        .split('/')
+        .zip(actual.split('/'))
+        .all(|(requested, actual)| requested == actual)
+}
+
+impl From<GenericContainerBuilder> for ContainerCreateOpts {
+    fn from(builder: GenericContainerBuilder) -> Self {
         let mut opts = ContainerCreateOpts::builder()
-            .image(self.image.to_string())
+            .image(builder.image.to_string())
             .env(
-                self.environment_variables
+                builder
+                    .environment_variables
                     .iter()
                     .map(|(name, value)| format!("{name}={value}")),
             )
-            .labels(self.labels)
-            .volumes(self.volumes)
-            .publish_all_ports();
+            .labels(builder.labels)
+            .volumes(builder.volumes)
+            .auto_remove(builder.auto_remove)
+            .privileged(builder.privileged);
 
-        if let Some(command) = self.command {
+        if let Some(runtime) = builder.oci_runtime {
+            opts = opts.runtime(runtime);
+        }
+        if let Some(stop_signal) = builder.stop_signal {
+            opts = opts.stop_signal(stop_signal);
+        }
+        if !builder.security_opts.is_empty() {
+            opts = opts.security_options(builder.security_opts);
+        }
+        if !builder.devices.is_empty() {
+            opts = opts.devices(builder.devices);
+        }
+        if !builder.volumes_from.is_empty() {
+            opts = opts.volumes_from(builder.volumes_from);
+        }
+        if let Some(name) = builder.container_name {
+            opts = opts.name(name);
+        }
+        if let Some(hostname) = builder.hostname {
+            opts = opts.hostname(hostname);
+        }
+        if let Some(domainname) = builder.domainname {
+            opts = opts.domainname(domainname);
+        }
+        if let Some(workdir) = builder.workdir {
+            opts = opts.working_dir(workdir);
+        }
+        if let Some(user) = builder.user {
+            opts = opts.user(user);
+        }
+
+        if let Some(command) = builder.command {
             opts = opts.command(command);
         }
+        if let Some(entrypoint) = builder.entrypoint {
+            opts = opts.entrypoint(entrypoint);
+        }
+        if !builder.extra_hosts.is_empty() {
+            opts = opts.extra_hosts(builder.extra_hosts);
+        }
 
-        for (exposed_port, host_port) in self.exposed_ports {
+        if let Some(network) = builder.network {
+            let endpoint = EndpointSettings {
+                aliases: Some(builder.network_aliases),
+                driver_opts: None,
+                endpoint_id: None,
+                gateway: None,
+                global_i_pv_6_address: None,
+                global_i_pv_6_prefix_len: None,
+                ipam_config: (builder.ipv4_address.is_some() || builder.ipv6_address.is_some())
+                    .then_some(EndpointIpamConfig {
+                        i_pv_4_address: builder.ipv4_address,
+                        i_pv_6_address: builder.ipv6_address,
+                        link_local_i_ps: None,
+                    }),
+                ip_address: None,
+                ip_prefix_len: None,
+                i_pv_6_gateway: None,
+                links: None,
+                mac_address: builder.mac_address,
+                network_id: None,
+            };
+            opts = opts
+                .network_mode(&network)
+                .network_config(NetworkingConfig {
+                    endpoints_config: Some(HashMap::from([(network, endpoint)])),
+                });
+        }
+
+        if let Some(network_mode) = builder.network_mode {
+            // ports can't be published when the container doesn't have its own network stack
+            return opts.network_mode(network_mode).build();
+        }
+
+        opts = opts.publish_all_ports();
+        for (exposed_port, host_port) in builder.exposed_ports {
             opts = if let Some(host_port) = host_port {
                 opts.expose(
                     exposed_port.parse().unwrap(),
@@ -139,84 +1157,447 @@ impl Into<ContainerCreateOpts> for GenericContainerBuilder {
     }
 }
 
-#[derive(Clone)]
-pub enum ReadyStrategy {
-    Command(Vec<String>),
-    LogMessageRegExp(Regex),
-    StateHealthy,
-    None,
+pub struct GenericContainer {
+    params: GenericContainerBuilder,
+    container: ContainerClient,
 }
 
-impl ReadyStrategy {
-    pub(crate) async fn wait(
-        &self,
-        container: &ContainerClient,
-        timeout: Duration,
-    ) -> Result<(), docker_api::Error> {
-        let timeout_instant = Instant::now() + timeout;
-        loop {
-            match self {
-                ReadyStrategy::Command(_command) => {
-                    todo!();
-                }
-                ReadyStrategy::LogMessageRegExp(regex) => {
-                    let logs = container.logs().await?;
-                    if regex.is_match(&logs) {
-                        return Ok(());
-                    }
-                }
-                ReadyStrategy::StateHealthy => {
-                    if let Some(health_state) = container.health_state().await? {
-                        if health_state == "".to_string() {
-                            return Ok(());
-                        }
-                    }
-                }
-                ReadyStrategy::None => return Ok(()),
-            }
-            if timeout_instant < Instant::now() {
-                break;
-            } else {
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+impl GenericContainer {
+    pub fn from_dockerfile<P: Into<PathBuf>>(context_dir: P) -> GenericContainerBuilder {
+        GenericContainerBuilder::new(DockerImage::from_build(
+            BuildImageInstructions::from_context_dir(context_dir.into()),
+        ))
+    }
+
+    // the build context only holds the Dockerfile, add files with with_build_context_file
+    pub fn from_dockerfile_content<S: Into<String>>(dockerfile: S) -> GenericContainerBuilder {
+        GenericContainerBuilder::new(DockerImage::from_build(
+            BuildImageInstructions::from_dockerfile_content(dockerfile.into()),
+        ))
+    }
+
+    pub fn from_image<S>(full_image_name: S) -> GenericContainerBuilder
+    where
+        S: TryInto<DockerImage> + Display + Clone,
+        S::Error: Display,
+    {
+        match full_image_name.clone().try_into() {
+            Ok(image) => GenericContainerBuilder::new(image::substitute(image)),
+            Err(error) => {
+                let mut builder = GenericContainerBuilder::new(DockerImage::unparsed(
+                    full_image_name.to_string(),
+                ));
+                builder
+                    .configuration_errors
+                    .push(format!("invalid image name {full_image_name}: {error}"));
+                builder
             }
         }
-        return Err(docker_api::Error::StringError(
-            "Container takes too much time to be ready".to_string(),
-        ));
     }
-}
 
-pub struct GenericContainer {
-    params: GenericContainerBuilder,
-    container: ContainerClient,
-}
+    pub fn id(&self) -> &str {
+        self.container.id()
+    }
 
-impl GenericContainer {
-    pub fn from_image<S: Into<DockerImage>>(full_image_name: S) -> GenericContainerBuilder {
-        GenericContainerBuilder::new(full_image_name.into())
+    pub fn image(&self) -> &DockerImage {
+        &self.params.image
     }
 
-    pub async fn start(&self) -> Result<(), docker_api::Error> {
-        self.container
+    #[cfg(feature = "raw-api")]
+    pub fn docker(&self) -> &docker_api::Docker {
+        self.container.docker()
+    }
+
+    #[cfg(feature = "raw-api")]
+    pub fn raw_container(&self) -> &docker_api::Container {
+        self.container.raw_container()
+    }
+
+    pub async fn name(&self) -> Result<String, TestcontainersError> {
+        Ok(self.container.inspect().await?.name)
+    }
+
+    // the raw inspect data, for details this crate doesn't model
+    pub async fn inspect(&self) -> Result<ContainerInspect200Response, TestcontainersError> {
+        self.container.inspect_raw().await
+    }
+
+    pub async fn start(self) -> Result<RunningContainer, TestcontainersError> {
+        let state = self
+            .container
             .start_and_wait(
                 &self.params.wait_strategy_on_startup,
                 self.params.start_timeout,
+                self.params.consecutive_successes,
             )
             .await?;
-        Ok(())
+        Ok(RunningContainer {
+            params: self.params,
+            container: self.container,
+            state,
+        })
     }
 
-    pub async fn stop(&self) -> Result<(), docker_api::Error> {
-        self.container.stop().await
+    pub(crate) fn client_handle(&self) -> ContainerClient {
+        self.container.duplicate()
     }
 
-    pub async fn kill(&self) -> Result<(), docker_api::Error> {
-        self.container.kill().await
+    pub async fn run_to_completion(self) -> Result<ExitResult, TestcontainersError> {
+        self.container
+            .start_and_wait(&ReadyStrategy::None, self.params.start_timeout, 1)
+            .await?;
+        let exit_code = self.container.wait_for_exit().await?;
+        Ok(ExitResult {
+            exit_code,
+            stdout: self.container.logs_with(&LogOptions::stdout()).await?,
+            stderr: self.container.logs_with(&LogOptions::stderr()).await?,
+        })
     }
 
+    pub async fn remove(self) -> Result<(), TestcontainersError> {
+        self.container.remove().await
+    }
+
+    pub async fn commit(&self, tag: &str) -> Result<DockerImage, TestcontainersError> {
+        commit(&self.container, tag).await
+    }
+
+    pub async fn logs(&self) -> Result<String, TestcontainersError> {
+        self.container.logs().await
+    }
+
+    pub async fn stdout(&self) -> Result<String, TestcontainersError> {
+        self.container.logs_with(&LogOptions::stdout()).await
+    }
+
+    pub async fn stderr(&self) -> Result<String, TestcontainersError> {
+        self.container.logs_with(&LogOptions::stderr()).await
+    }
+
+    pub fn log_stream(
+        &self,
+        options: &LogOptions,
+    ) -> BoxStream<'static, Result<String, TestcontainersError>> {
+        self.container.log_stream(options)
+    }
+}
+
+pub struct RunningContainer {
+    params: GenericContainerBuilder,
+    container: ContainerClient,
+    state: RunningState,
+}
+
+impl RunningContainer {
+    pub async fn stop(self) -> Result<GenericContainer, TestcontainersError> {
+        self.container
+            .stop(self.params.stop_signal.as_deref(), self.params.stop_timeout)
+            .await?;
+        println!("🐋 Container {} is stopped", self.state.name);
+        Ok(GenericContainer {
+            params: self.params,
+            container: self.container.into_stopped(),
+        })
+    }
+
+    pub async fn kill(self) -> Result<GenericContainer, TestcontainersError> {
+        self.container.kill().await?;
+        println!("🐋 Container {} killed", self.state.name);
+        Ok(GenericContainer {
+            params: self.params,
+            container: self.container.into_stopped(),
+        })
+    }
+
+    pub async fn stop_and_remove(self) -> Result<(), TestcontainersError> {
+        self.stop().await?.remove().await
+    }
+
+    pub(crate) fn client_handle(&self) -> ContainerClient {
+        self.container.duplicate()
+    }
+
+    pub(crate) async fn wait_until(
+        &self,
+        strategy: &ReadyStrategy,
+    ) -> Result<(), TestcontainersError> {
+        strategy
+            .wait(&self.container, self.params.start_timeout, 1)
+            .await
+    }
+
+    pub async fn exec(&self, command_parts: &[&str]) -> Result<ExecResult, TestcontainersError> {
+        self.exec_with(command_parts, &ExecOptions::default()).await
+    }
+
+    pub async fn exec_with(
+        &self,
+        command_parts: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ExecResult, TestcontainersError> {
+        let command: Vec<String> = command_parts.iter().map(|s| s.to_string()).collect();
+        self.container.exec_with(&command, options).await
+    }
+
+    pub async fn exec_stream(
+        &self,
+        command_parts: &[&str],
+        options: &ExecOptions,
+    ) -> Result<BoxStream<'static, Result<ExecOutput, TestcontainersError>>, TestcontainersError>
+    {
+        let command: Vec<String> = command_parts.iter().map(|s| s.to_string()).collect();
+        self.container.exec_stream(&command, options).await
+    }
+
+    pub async fn logs(&self) -> Result<String, TestcontainersError> {
+        self.container.logs().await
+    }
+
+    pub async fn stdout(&self) -> Result<String, TestcontainersError> {
+        self.container.logs_with(&LogOptions::stdout()).await
+    }
+
+    pub async fn stderr(&self) -> Result<String, TestcontainersError> {
+        self.container.logs_with(&LogOptions::stderr()).await
+    }
+
+    pub fn log_stream(
+        &self,
+        options: &LogOptions,
+    ) -> BoxStream<'static, Result<String, TestcontainersError>> {
+        self.container.log_stream(options)
+    }
+
+    pub async fn copy_file_to_container<P: AsRef<Path>>(
+        &self,
+        host_path: P,
+        container_path: &str,
+    ) -> Result<(), TestcontainersError> {
+        let contents = std::fs::read(host_path)?;
+        self.container
+            .copy_file_into(container_path, &contents)
+            .await
+    }
+
+    pub async fn copy_string_to_file(
+        &self,
+        contents: &str,
+        container_path: &str,
+    ) -> Result<(), TestcontainersError> {
+        self.container
+            .copy_file_into(container_path, contents.as_bytes())
+            .await
+    }
+
+    pub async fn copy_file_from_container(
+        &self,
+        container_path: &str,
+    ) -> Result<Vec<u8>, TestcontainersError> {
+        self.container.copy_file_from(container_path).await
+    }
+
+    pub async fn copy_archive_from_container(
+        &self,
+        container_path: &str,
+    ) -> Result<Vec<u8>, TestcontainersError> {
+        self.container.copy_archive_from(container_path).await
+    }
+
+    // the container is paused while its filesystem is snapshotted
+    pub async fn commit(&self, tag: &str) -> Result<DockerImage, TestcontainersError> {
+        commit(&self.container, tag).await
+    }
+
+    pub async fn get_platform(&self) -> Result<Option<String>, TestcontainersError> {
+        self.container.platform().await
+    }
+
+    pub fn id(&self) -> &str {
+        &self.state.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.state.name
+    }
+
+    pub fn image(&self) -> &DockerImage {
+        &self.params.image
+    }
+
+    #[cfg(feature = "raw-api")]
+    pub fn docker(&self) -> &docker_api::Docker {
+        self.container.docker()
+    }
+
+    #[cfg(feature = "raw-api")]
+    pub fn raw_container(&self) -> &docker_api::Container {
+        self.container.raw_container()
+    }
+
+    pub async fn inspect(&self) -> Result<ContainerInspect200Response, TestcontainersError> {
+        self.container.inspect_raw().await
+    }
+
+    pub fn get_env(&self, key: &str) -> Option<&str> {
+        self.params
+            .environment_variables
+            .get(key)
+            .map(String::as_str)
+    }
+
+    pub fn get_host(&self) -> String {
+        self.container.host()
+    }
+
+    pub fn get_container_ip(&self, network: &str) -> Option<String> {
+        self.state.networks.get(network)?.ip_address.clone()
+    }
+
+    pub fn get_gateway_ip(&self) -> Option<String> {
+        self.state.gateway.clone()
+    }
+
+    // the host ports in the order of the container ports, None unless every port is mapped
+    pub fn get_host_port_range(
+        &self,
+        ports: RangeInclusive<u16>,
+        protocol: Protocol,
+    ) -> Option<Vec<u16>> {
+        ports
+            .map(|number| self.get_host_port(ContainerPort { number, protocol }))
+            .collect()
+    }
+
+    // either a ContainerPort or a "5432/tcp" spec, a bare port number stands for tcp
     pub fn get_host_port<S: Into<String>>(&self, container_port_spec: S) -> Option<u16> {
-        let container_port_spec: String = container_port_spec.into();
-        let ro_state = self.container.running_state.read().unwrap();
-        ro_state.as_ref()?.ports.get(&container_port_spec).copied()
+        self.try_get_host_port(container_port_spec).ok()
+    }
+
+    pub fn try_get_host_port<S: Into<String>>(
+        &self,
+        container_port_spec: S,
+    ) -> Result<u16, PortError> {
+        let container_port: ContainerPort = container_port_spec
+            .into()
+            .parse()
+            .map_err(PortError::InvalidSpec)?;
+        if !self.state.running {
+            return Err(PortError::NotRunning);
+        }
+        if self.params.network_mode.as_deref() == Some("host") {
+            return Ok(container_port.number);
+        }
+        let spec = container_port.to_string();
+        if let Some(host_port) = self.state.ports.get(&spec) {
+            return Ok(*host_port);
+        }
+        if self.params.exposed_ports.contains_key(&spec) || self.state.exposed_ports.contains(&spec)
+        {
+            return Err(PortError::NotMapped(container_port));
+        }
+        Err(PortError::NotExposed(container_port))
+    }
+
+    // the host port of the lowest mapped container port, handy for single port containers
+    pub fn get_first_mapped_port(&self) -> Result<u16, PortError> {
+        if !self.state.running {
+            return Err(PortError::NotRunning);
+        }
+        self.state
+            .ports
+            .iter()
+            .filter_map(|(spec, host_port)| Some((spec.parse::<ContainerPort>().ok()?, host_port)))
+            .min_by_key(|(container_port, _)| (container_port.number, container_port.to_string()))
+            .map(|(_, host_port)| *host_port)
+            .ok_or(PortError::NoMappedPort)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configuration_hash_ignores_declaration_order() {
+        let first = GenericContainer::from_image("postgres:latest")
+            .add_env("POSTGRES_USER", "test")
+            .add_env("POSTGRES_DB", "test")
+            .add_exposed_tcp_port(5432);
+        let second = GenericContainer::from_image("postgres:latest")
+            .add_exposed_tcp_port(5432)
+            .add_env("POSTGRES_DB", "test")
+            .add_env("POSTGRES_USER", "test");
+        assert_eq!(first.configuration_hash(), second.configuration_hash());
+    }
+
+    #[test]
+    fn configuration_hash_depends_on_configuration() {
+        let first = GenericContainer::from_image("postgres:latest").add_env("POSTGRES_DB", "a");
+        let second = GenericContainer::from_image("postgres:latest").add_env("POSTGRES_DB", "b");
+        assert_ne!(first.configuration_hash(), second.configuration_hash());
+    }
+
+    #[test]
+    fn platform_variant_is_only_compared_when_specified() {
+        assert!(platform_matches("linux/arm64", "linux/arm64/v8"));
+        assert!(platform_matches("linux/arm64/v8", "linux/arm64"));
+        assert!(!platform_matches("linux/amd64", "linux/arm64"));
+        assert!(!platform_matches("linux/arm/v6", "linux/arm/v7"));
+    }
+
+    #[test]
+    fn directories_are_copied_file_by_file() {
+        let host_dir = std::env::temp_dir().join("testcontainers-copy-dir");
+        std::fs::create_dir_all(host_dir.join("css")).unwrap();
+        std::fs::write(host_dir.join("index.html"), "<h1>hello</h1>").unwrap();
+        std::fs::write(host_dir.join("css/site.css"), "h1 {}").unwrap();
+        let builder = GenericContainer::from_image("nginx:alpine")
+            .copy_dir_to_container(&host_dir, "/usr/share/nginx/html/");
+        let container_paths: Vec<_> = builder
+            .files_to_copy
+            .iter()
+            .map(|(_, container_path)| container_path.as_str())
+            .collect();
+        assert_eq!(
+            container_paths,
+            [
+                "/usr/share/nginx/html/css/site.css",
+                "/usr/share/nginx/html/index.html"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_configuration_is_reported_on_create() {
+        let error = GenericContainer::from_image("postgres:latest@invalid")
+            .wait_for_log_on_startup("(unclosed")
+            .with_start_timeout("forever")
+            .create()
+            .await
+            .err()
+            .unwrap();
+        let TestcontainersError::InvalidConfiguration(message) = error else {
+            panic!("expected an invalid configuration error but it was {error}");
+        };
+        assert!(message.contains("invalid image name postgres:latest@invalid"));
+        assert!(message.contains("invalid log regular expression (unclosed"));
+        assert!(message.contains("invalid start timeout forever"));
+    }
+
+    #[tokio::test]
+    async fn build_options_require_a_dockerfile() {
+        let error = GenericContainer::from_image("postgres:latest")
+            .with_build_arg("VERSION", "1.0")
+            .create()
+            .await
+            .err()
+            .unwrap();
+        let TestcontainersError::InvalidConfiguration(message) = error else {
+            panic!("expected an invalid configuration error but it was {error}");
+        };
+        assert_eq!(
+            message,
+            "build arg requires an image built from a dockerfile"
+        );
     }
 }
@@ -0,0 +1,250 @@
+use crate::{
+    container::{GenericContainer, GenericContainerBuilder},
+    error::TestcontainersError,
+};
+use serde::{Deserialize, Serialize};
+use serde_yaml::with::singleton_map_recursive;
+use std::{collections::HashMap, fmt::Display, path::Path};
+
+// a container described as data so that it can be shared with other repositories and languages,
+// e.g. a testcontainers.toml:
+//
+//     image = "postgres:16"
+//     ports = ["5432", "15433:5433/tcp"]
+//     start_timeout = "60s"
+//
+//     [env]
+//     POSTGRES_PASSWORD = "test"
+//
+//     [wait]
+//     log = "ready to accept connections"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerDefinition {
+    pub image: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    // container port specs, optionally prefixed by a fixed host port: "5432", "15432:5432/tcp"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volumes: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<WaitDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_timeout: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitDefinition {
+    Log(String),
+    Port(u16),
+    Command(Vec<String>),
+    File(String),
+    Healthy,
+    Exit,
+}
+
+impl ContainerDefinition {
+    // the format follows the file extension: toml, yaml, yml or json
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, TestcontainersError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            Some("yaml" | "yml") => Self::from_yaml(&contents),
+            Some("json") => Self::from_json(&contents),
+            _ => Err(TestcontainersError::InvalidConfiguration(format!(
+                "unsupported container definition format: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, TestcontainersError> {
+        toml::from_str(contents).map_err(invalid_definition)
+    }
+
+    // serde_yaml writes enums as yaml tags by default, the wait strategy is a map like in the
+    // other formats
+    pub fn from_yaml(contents: &str) -> Result<Self, TestcontainersError> {
+        singleton_map_recursive::deserialize(serde_yaml::Deserializer::from_str(contents))
+            .map_err(invalid_definition)
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self, TestcontainersError> {
+        serde_json::from_str(contents).map_err(invalid_definition)
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("definitions are always serializable")
+    }
+
+    pub fn to_yaml(&self) -> String {
+        let mut yaml = Vec::new();
+        singleton_map_recursive::serialize(self, &mut serde_yaml::Serializer::new(&mut yaml))
+            .expect("definitions are always serializable");
+        String::from_utf8(yaml).expect("yaml is utf-8")
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("definitions are always serializable")
+    }
+
+    pub fn into_builder(self) -> Result<GenericContainerBuilder, TestcontainersError> {
+        let mut builder = GenericContainer::from_image(self.image.as_str());
+        for (key, value) in self.env {
+            builder = builder.add_env(key, value);
+        }
+        for port in &self.ports {
+            let (host_port, container_port_spec) = parse_port(port)?;
+            builder = builder.add_exposed_port(host_port, container_port_spec);
+        }
+        for volume in self.volumes {
+            builder = builder.add_volume(volume);
+        }
+        for (name, value) in self.labels {
+            builder = builder.add_label(name, value);
+        }
+        if let Some(command) = &self.command {
+            let command: Vec<&str> = command.iter().map(String::as_str).collect();
+            builder = builder.with_command(&command);
+        }
+        builder = match self.wait {
+            Some(WaitDefinition::Log(regex)) => builder.wait_for_log_on_startup(regex),
+            Some(WaitDefinition::Port(port)) => builder.wait_for_port_listening(port),
+            Some(WaitDefinition::Command(command)) => {
+                let command: Vec<&str> = command.iter().map(String::as_str).collect();
+                builder.wait_for_command_on_startup(&command)
+            }
+            Some(WaitDefinition::File(path)) => builder.wait_for_file_on_startup(path),
+            Some(WaitDefinition::Healthy) => builder.wait_for_healthy(),
+            Some(WaitDefinition::Exit) => builder.wait_for_exit(),
+            None => builder,
+        };
+        if let Some(start_timeout) = &self.start_timeout {
+            builder = builder.with_start_timeout(start_timeout);
+        }
+        Ok(builder)
+    }
+}
+
+fn invalid_definition<E: Display>(error: E) -> TestcontainersError {
+    TestcontainersError::InvalidConfiguration(format!("invalid container definition: {error}"))
+}
+
+fn parse_port(port: &str) -> Result<(Option<u16>, String), TestcontainersError> {
+    let invalid = || TestcontainersError::InvalidConfiguration(format!("invalid port {port}"));
+    let (host_port, container_port) = match port.split_once(':') {
+        Some((host_port, container_port)) => (
+            Some(host_port.parse().map_err(|_| invalid())?),
+            container_port,
+        ),
+        None => (None, port),
+    };
+    let (number, protocol) = container_port
+        .split_once('/')
+        .unwrap_or((container_port, "tcp"));
+    let number: u16 = number.parse().map_err(|_| invalid())?;
+    Ok((host_port, format!("{number}/{protocol}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn definitions_are_read_from_json() {
+        let definition = ContainerDefinition::from_json(
+            r#"{
+                "image": "postgres:16",
+                "env": { "POSTGRES_PASSWORD": "test" },
+                "ports": ["5432", "15433:5433/udp"],
+                "wait": { "log": "ready to accept connections" },
+                "start_timeout": "60s"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(definition.image, "postgres:16");
+        assert_eq!(
+            definition.wait,
+            Some(WaitDefinition::Log(
+                "ready to accept connections".to_string()
+            ))
+        );
+        assert_eq!(
+            ContainerDefinition::from_json(&definition.to_json()).unwrap(),
+            definition
+        );
+        assert!(definition.into_builder().is_ok());
+        assert!(ContainerDefinition::from_json(r#"{ "image": "redis", "name": "x" }"#).is_err());
+    }
+
+    #[test]
+    fn definitions_are_read_from_toml_and_yaml() {
+        let from_toml = ContainerDefinition::from_toml(
+            r#"
+            image = "postgres:16"
+            ports = ["5432", "15433:5433/udp"]
+            start_timeout = "60s"
+
+            [env]
+            POSTGRES_PASSWORD = "test"
+
+            [wait]
+            log = "ready to accept connections"
+            "#,
+        )
+        .unwrap();
+        let from_yaml = ContainerDefinition::from_yaml(
+            r#"
+            image: postgres:16
+            env:
+              POSTGRES_PASSWORD: test
+            ports: ["5432", "15433:5433/udp"]
+            wait:
+              log: ready to accept connections
+            start_timeout: 60s
+            "#,
+        )
+        .unwrap();
+        assert_eq!(from_toml, from_yaml);
+        assert_eq!(
+            ContainerDefinition::from_toml(&from_toml.to_toml()).unwrap(),
+            from_toml
+        );
+        assert_eq!(
+            ContainerDefinition::from_yaml(&from_yaml.to_yaml()).unwrap(),
+            from_yaml
+        );
+        let healthy = ContainerDefinition {
+            image: "redis".to_string(),
+            wait: Some(WaitDefinition::Healthy),
+            ..ContainerDefinition::default()
+        };
+        assert_eq!(
+            ContainerDefinition::from_toml(&healthy.to_toml()).unwrap(),
+            healthy
+        );
+        assert_eq!(
+            ContainerDefinition::from_yaml(&healthy.to_yaml()).unwrap(),
+            healthy
+        );
+        assert!(ContainerDefinition::from_toml("image = \"redis\"\nname = \"x\"").is_err());
+    }
+
+    #[test]
+    fn ports_accept_fixed_host_ports_and_protocols() {
+        assert_eq!(parse_port("5432").unwrap(), (None, "5432/tcp".to_string()));
+        assert_eq!(
+            parse_port("15433:5433/udp").unwrap(),
+            (Some(15433), "5433/udp".to_string())
+        );
+        assert!(parse_port("http").is_err());
+    }
+}
@@ -1,33 +1,217 @@
-use crate::{container::ReadyStrategy, image::DockerImage};
+use crate::{
+    config::Config,
+    engine::EngineClient,
+    error::TestcontainersError,
+    image::{DockerImage, ImageArchive},
+    runtime::{ContainerRuntime, EngineCreateOpts},
+    wait::ReadyStrategy,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use docker_api::{
+    conn::TtyChunk,
     models::{ContainerInspect200Response, ImageBuildChunk, NetworkSettings},
     opts::{
-        ContainerCreateOpts, ContainerStopOpts, ExecCreateOpts, ExecStartOpts, ImageBuildOpts,
-        ImageFilter, ImageListOpts, LogsOpts, PullOpts,
+        ContainerCommitOpts, ContainerCreateOpts, ContainerFilter, ContainerListOpts,
+        ContainerRemoveOpts, ContainerStopOpts, ExecCreateOpts, ExecStartOpts, ImageListOpts,
+        LogsOpts, NetworkCreateOpts, NetworkFilter, NetworkListOpts, PullOpts, RegistryAuth,
+        VolumeCreateOpts, VolumeFilter, VolumeListOpts,
     },
-    Container, Docker,
+    Container, Docker, Exec, Network, Volume,
 };
-use futures_util::StreamExt;
+use futures_util::{stream::BoxStream, Stream, StreamExt, TryStreamExt};
 use log::{debug, error};
-use std::{collections::HashMap, fmt::Display, sync::RwLock, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+    time::{Duration, SystemTime},
+};
+use tokio::sync::mpsc;
+
+const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+const STARTUP_FAILURE_LOG_LINES: usize = 50;
+
+static SHARED_CLIENT: OnceLock<Arc<DockerClient>> = OnceLock::new();
+
+pub(crate) fn resolve_host(docker_host: Option<&str>) -> String {
+    if let Some(host) = Config::global().host_override() {
+        return host.to_string();
+    }
+    if let Some(docker_host) = docker_host {
+        if let Some((_scheme, address)) = docker_host
+            .split_once("://")
+            .filter(|(scheme, _)| ["tcp", "http", "https"].contains(scheme))
+        {
+            let address = address.split('/').next().unwrap_or(address);
+            let host = address
+                .rsplit_once(':')
+                .map_or(address, |(host, _port)| host);
+            return host.to_string();
+        }
+    }
+    // containers started by a containerized test process are reachable through its gateway
+    if Path::new("/.dockerenv").exists() {
+        if let Some(gateway) = std::fs::read_to_string("/proc/net/route")
+            .ok()
+            .and_then(|routes| default_gateway(&routes))
+        {
+            return gateway;
+        }
+    }
+    "localhost".to_string()
+}
 
-pub(crate) struct DockerClient {
+fn default_gateway(routes: &str) -> Option<String> {
+    routes.lines().skip(1).find_map(|route| {
+        let fields: Vec<_> = route.split_whitespace().collect();
+        match fields.as_slice() {
+            [_interface, "00000000", gateway, ..] => {
+                let gateway = u32::from_str_radix(gateway, 16).ok()?;
+                Some(Ipv4Addr::from(gateway.to_le_bytes()).to_string())
+            }
+            _ => None,
+        }
+    })
+}
+
+pub struct DockerClient {
     docker: Docker,
+    docker_host: Option<String>,
+    daemon_socket: String,
+    engine: Option<EngineClient>,
 }
 
 impl Default for DockerClient {
     fn default() -> Self {
+        Self::connect(None, None).unwrap_or_else(|error| {
+            error!("🐋 Unsupported docker host: {error}");
+            Self {
+                docker: Docker::unix(DEFAULT_DOCKER_SOCKET),
+                docker_host: None,
+                daemon_socket: DEFAULT_DOCKER_SOCKET.to_string(),
+                engine: Some(EngineClient::unix(DEFAULT_DOCKER_SOCKET)),
+            }
+        })
+    }
+}
+
+impl From<Docker> for DockerClient {
+    fn from(docker: Docker) -> Self {
+        // the engine client cannot be derived from an existing Docker
         Self {
-            docker: Docker::unix("/var/run/docker.sock"),
+            docker,
+            docker_host: None,
+            daemon_socket: daemon_socket(None),
+            engine: None,
         }
     }
 }
 
 impl DockerClient {
-    pub(crate) async fn image_exists_locally(
+    pub fn shared() -> Arc<DockerClient> {
+        SHARED_CLIENT
+            .get_or_init(|| Arc::new(DockerClient::default()))
+            .clone()
+    }
+
+    // only effective before the first container or network is created
+    pub fn set_shared(client: DockerClient) -> Result<(), TestcontainersError> {
+        SHARED_CLIENT.set(Arc::new(client)).map_err(|_| {
+            TestcontainersError::InvalidConfiguration(
+                "the shared docker client is already initialized".to_string(),
+            )
+        })
+    }
+
+    pub fn connect(
+        docker_host: Option<&str>,
+        tls: Option<&DockerTls>,
+    ) -> Result<Self, TestcontainersError> {
+        let config = Config::global();
+        let docker_host = docker_host.or(config.docker_host());
+        let tls = tls.cloned().or_else(|| DockerTls::from_config(config));
+        let (docker, engine, socket) = match (docker_host, tls) {
+            (Some(docker_host), Some(tls)) if !docker_host.starts_with("unix://") => {
+                let (docker, engine) = connect_tls(docker_host, &tls)?;
+                (docker, engine, None)
+            }
+            (Some(docker_host), _) => (
+                Docker::new(docker_host)?,
+                EngineClient::new(docker_host)?,
+                docker_host.strip_prefix("unix://").map(PathBuf::from),
+            ),
+            (None, _) => {
+                let socket = discover_docker_socket();
+                (
+                    Docker::unix(&socket),
+                    EngineClient::unix(&socket),
+                    Some(socket),
+                )
+            }
+        };
+        Ok(Self {
+            docker,
+            docker_host: docker_host.map(String::from),
+            daemon_socket: daemon_socket(socket.as_deref()),
+            engine: Some(engine),
+        })
+    }
+
+    // containers that are already started, e.g. by docker compose
+    pub(crate) async fn list_started(
         &self,
-        image: &DockerImage,
-    ) -> Result<bool, docker_api::Error> {
+        label: &str,
+        value: &str,
+    ) -> Result<Vec<(HashMap<String, String>, ContainerClient)>, TestcontainersError> {
+        let containers = self
+            .docker
+            .containers()
+            .list(
+                &ContainerListOpts::builder()
+                    .all(true)
+                    .filter(vec![ContainerFilter::Label(label.into(), value.into())])
+                    .build(),
+            )
+            .await?;
+        Ok(containers
+            .into_iter()
+            .filter_map(|summary| Some((summary.labels.unwrap_or_default(), summary.id?)))
+            .map(|(labels, id)| {
+                let container = ContainerClient::reused(
+                    self.docker.clone(),
+                    self.docker_host.clone(),
+                    self.docker.containers().get(id),
+                );
+                (labels, container)
+            })
+            .collect())
+    }
+
+    // the connection of a Docker is private, so a client made with DockerClient::from(Docker)
+    // has no engine client
+    fn engine(&self, purpose: &str) -> Result<&EngineClient, docker_api::Error> {
+        self.engine.as_ref().ok_or_else(|| {
+            docker_api::Error::StringError(format!(
+                "{purpose} requires a client created with DockerClient::connect, not DockerClient::from(Docker)"
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl ContainerRuntime for DockerClient {
+    fn docker_host(&self) -> Option<&str> {
+        self.docker_host.as_deref()
+    }
+
+    fn daemon_socket(&self) -> &str {
+        &self.daemon_socket
+    }
+
+    async fn image_exists_locally(&self, image: &DockerImage) -> Result<bool, TestcontainersError> {
         let images = self
             .docker
             .images()
@@ -40,37 +224,374 @@ impl DockerClient {
         Ok(!images.is_empty())
     }
 
-    pub(crate) async fn pull(&self, image: &DockerImage) -> Result<(), docker_api::Error> {
-        let images = self.docker.images();
-        let mut stream = images.pull(&PullOpts::builder().image(image.get_full_name()).build());
-        while let Some(build_chunk) = stream.next().await {
-            match build_chunk {
-                Ok(build_chunk) => debug!("{}", Loggable::from(build_chunk)),
-                Err(error) => return Err(error),
-            }
-        }
-        Ok(())
+    async fn image_platform(
+        &self,
+        image: &DockerImage,
+    ) -> Result<Option<String>, TestcontainersError> {
+        let inspect = self
+            .docker
+            .images()
+            .get(image.get_full_name())
+            .inspect()
+            .await?;
+        Ok(inspect
+            .os
+            .zip(inspect.architecture)
+            .map(|(os, architecture)| format!("{os}/{architecture}")))
     }
 
-    pub(crate) async fn build(&self, build_opts: &ImageBuildOpts) -> Result<(), docker_api::Error> {
-        let images = self.docker.images();
-        let mut stream = images.build(&build_opts);
-        while let Some(build_chunk) = stream.next().await {
-            match build_chunk {
-                Ok(build_chunk) => debug!("{}", Loggable::from(build_chunk)),
-                Err(error) => return Err(error),
+    async fn pull(
+        &self,
+        image: &DockerImage,
+        platform: Option<&str>,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<(), docker_api::Error> {
+        match platform {
+            Some(platform) => {
+                let engine = self.engine("pulling an image for a platform")?;
+                follow_progress(engine.pull(&image.get_full_name(), platform, auth)).await
+            }
+            None => {
+                let mut opts = PullOpts::builder().image(image.get_full_name());
+                if let Some(auth) = auth {
+                    opts = opts.auth(auth.clone());
+                }
+                follow_progress(self.docker.images().pull(&opts.build())).await
             }
         }
-        Ok(())
     }
 
-    pub(crate) async fn create(
+    async fn build(
+        &self,
+        image: &DockerImage,
+        platform: Option<&str>,
+        auths: &HashMap<String, RegistryAuth>,
+    ) -> Result<(), docker_api::Error> {
+        let build = image.build_instructions().ok_or_else(|| {
+            docker_api::Error::StringError(format!("no build instructions for image {image}"))
+        })?;
+        follow_progress(self.engine("building an image")?.build(
+            &image.get_full_name(),
+            build,
+            platform,
+            auths,
+        ))
+        .await
+    }
+
+    async fn load(&self, archive: &ImageArchive) -> Result<(), docker_api::Error> {
+        let tarball = std::fs::File::open(archive.path())?;
+        follow_progress(self.docker.images().import(tarball)).await
+    }
+
+    async fn create(
         &self,
         opts: &ContainerCreateOpts,
+        engine_opts: &EngineCreateOpts,
     ) -> Result<ContainerClient, docker_api::Error> {
+        let container = match engine_opts.is_empty() {
+            true => self.docker.containers().create(opts).await?,
+            false => {
+                let engine = self.engine(&engine_opts.settings().join(", "))?;
+                let id = engine.create_container(opts, engine_opts).await?;
+                self.docker.containers().get(id)
+            }
+        };
         Ok(ContainerClient::new(
-            self.docker.containers().create(&opts).await?,
+            self.docker.clone(),
+            self.docker_host.clone(),
+            container,
+        ))
+    }
+
+    async fn create_network(
+        &self,
+        opts: &NetworkCreateOpts,
+    ) -> Result<Network, TestcontainersError> {
+        Ok(self.docker.networks().create(opts).await?)
+    }
+
+    async fn create_volume(&self, opts: &VolumeCreateOpts) -> Result<Volume, TestcontainersError> {
+        let volume = self.docker.volumes().create(opts).await?;
+        Ok(self.docker.volumes().get(volume.name))
+    }
+
+    async fn find_running(
+        &self,
+        label: &str,
+        value: &str,
+    ) -> Result<Option<ContainerClient>, TestcontainersError> {
+        let containers = self
+            .docker
+            .containers()
+            .list(
+                &ContainerListOpts::builder()
+                    .filter(vec![ContainerFilter::Label(label.into(), value.into())])
+                    .build(),
+            )
+            .await?;
+        Ok(containers
+            .into_iter()
+            .find_map(|summary| summary.id)
+            .map(|id| {
+                ContainerClient::reused(
+                    self.docker.clone(),
+                    self.docker_host.clone(),
+                    self.docker.containers().get(id),
+                )
+            }))
+    }
+
+    async fn list_labeled(
+        &self,
+        label: &str,
+    ) -> Result<Vec<HashMap<String, String>>, TestcontainersError> {
+        let containers = self
+            .docker
+            .containers()
+            .list(
+                &ContainerListOpts::builder()
+                    .all(true)
+                    .filter(vec![ContainerFilter::LabelKey(label.into())])
+                    .build(),
+            )
+            .await?;
+        let networks = self
+            .docker
+            .networks()
+            .list(
+                &NetworkListOpts::builder()
+                    .filter(vec![NetworkFilter::LabelKey(label.into())])
+                    .build(),
+            )
+            .await?;
+        let volumes = self
+            .docker
+            .volumes()
+            .list(
+                &VolumeListOpts::builder()
+                    .filter(vec![VolumeFilter::LabelKey(label.into())])
+                    .build(),
+            )
+            .await?;
+        Ok(containers
+            .into_iter()
+            .filter_map(|container| container.labels)
+            .chain(networks.into_iter().filter_map(|network| network.labels))
+            .chain(
+                volumes
+                    .volumes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|volume| volume.labels),
+            )
+            .collect())
+    }
+
+    // containers go first since networks and volumes can't be removed while in use
+    async fn remove_labeled(&self, label: &str, value: &str) -> Result<(), TestcontainersError> {
+        let containers = self
+            .docker
+            .containers()
+            .list(
+                &ContainerListOpts::builder()
+                    .all(true)
+                    .filter(vec![ContainerFilter::Label(label.into(), value.into())])
+                    .build(),
+            )
+            .await?;
+        for id in containers.into_iter().filter_map(|container| container.id) {
+            ContainerClient::new(self.docker.clone(), None, self.docker.containers().get(id))
+                .remove()
+                .await?;
+        }
+        let networks = self
+            .docker
+            .networks()
+            .list(
+                &NetworkListOpts::builder()
+                    .filter(vec![NetworkFilter::LabelKeyVal(label.into(), value.into())])
+                    .build(),
+            )
+            .await?;
+        for id in networks.into_iter().filter_map(|network| network.id) {
+            self.docker.networks().get(id).delete().await?;
+        }
+        let volumes = self
+            .docker
+            .volumes()
+            .list(
+                &VolumeListOpts::builder()
+                    .filter(vec![VolumeFilter::Label {
+                        key: label.into(),
+                        val: value.into(),
+                    }])
+                    .build(),
+            )
+            .await?;
+        for volume in volumes.volumes.unwrap_or_default() {
+            self.docker.volumes().get(volume.name).delete().await?;
+        }
+        Ok(())
+    }
+}
+
+async fn follow_progress<S>(mut stream: S) -> Result<(), docker_api::Error>
+where
+    S: Stream<Item = Result<ImageBuildChunk, docker_api::Error>> + Unpin,
+{
+    while let Some(build_chunk) = stream.next().await {
+        match build_chunk? {
+            // registry and build failures are reported within the stream rather than as http errors
+            ImageBuildChunk::Error { error, .. } => {
+                return Err(docker_api::Error::StringError(error))
+            }
+            build_chunk => debug!("{}", Loggable::from(build_chunk)),
+        }
+    }
+    Ok(())
+}
+
+fn discover_docker_socket() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+    docker_socket_candidates(home.as_deref(), runtime_dir.as_deref())
+        .into_iter()
+        .find(|socket| socket.exists())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DOCKER_SOCKET))
+}
+
+fn daemon_socket(socket: Option<&Path>) -> String {
+    if let Some(socket) = Config::global().docker_socket_override() {
+        return socket.to_string();
+    }
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+    daemon_side_socket(socket, runtime_dir.as_deref())
+}
+
+// rootless dockerd and podman run next to the tests and see their socket where the tests do,
+// Docker Desktop and colima run in a VM where the socket is at the default path
+fn daemon_side_socket(socket: Option<&Path>, runtime_dir: Option<&Path>) -> String {
+    match socket {
+        Some(socket)
+            if runtime_dir.is_some_and(|dir| socket.starts_with(dir))
+                || socket.starts_with("/run/podman") =>
+        {
+            socket.display().to_string()
+        }
+        _ => DEFAULT_DOCKER_SOCKET.to_string(),
+    }
+}
+
+// probed in order: the classic root socket, rootless dockerd, Docker Desktop on macOS and
+// linux, colima, then rootless and root podman
+fn docker_socket_candidates(home: Option<&Path>, runtime_dir: Option<&Path>) -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from(DEFAULT_DOCKER_SOCKET)];
+    if let Some(runtime_dir) = runtime_dir {
+        candidates.push(runtime_dir.join("docker.sock"));
+    }
+    if let Some(home) = home {
+        candidates.push(home.join(".docker/run/docker.sock"));
+        candidates.push(home.join(".docker/desktop/docker.sock"));
+        candidates.push(home.join(".colima/default/docker.sock"));
+        candidates.push(home.join(".colima/docker.sock"));
+    }
+    if let Some(runtime_dir) = runtime_dir {
+        candidates.push(runtime_dir.join("podman/podman.sock"));
+    }
+    candidates.push(PathBuf::from("/run/podman/podman.sock"));
+    candidates
+}
+
+#[cfg(feature = "tls")]
+fn connect_tls(
+    docker_host: &str,
+    tls: &DockerTls,
+) -> Result<(Docker, EngineClient), TestcontainersError> {
+    let address = docker_host
+        .split_once("://")
+        .map_or(docker_host, |(_scheme, address)| address);
+    let connect = |cert_path: &Path| -> Result<_, TestcontainersError> {
+        Ok((
+            Docker::tls(address, cert_path, tls.verify)?,
+            EngineClient::tls(address, cert_path, tls.verify)?,
         ))
+    };
+    match &tls.files {
+        TlsFiles::CertPath(cert_path) => connect(cert_path),
+        // the clients only read a directory laid out like the one of the docker CLI, the
+        // copies go to a private directory removed once the clients have loaded them
+        TlsFiles::Pem { ca, cert, key } => {
+            let cert_path = tempfile::tempdir()?;
+            for (file, name) in [(ca, "ca.pem"), (cert, "cert.pem"), (key, "key.pem")] {
+                std::fs::copy(file, cert_path.path().join(name))?;
+            }
+            connect(cert_path.path())
+        }
+    }
+}
+
+#[cfg(not(feature = "tls"))]
+fn connect_tls(
+    docker_host: &str,
+    _tls: &DockerTls,
+) -> Result<(Docker, EngineClient), TestcontainersError> {
+    Err(TestcontainersError::InvalidConfiguration(format!(
+        "connecting to {docker_host} over TLS requires the tls feature"
+    )))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DockerTls {
+    files: TlsFiles,
+    verify: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TlsFiles {
+    CertPath(PathBuf),
+    Pem {
+        ca: PathBuf,
+        cert: PathBuf,
+        key: PathBuf,
+    },
+}
+
+impl DockerTls {
+    // the directory is expected to hold ca.pem, cert.pem and key.pem like the docker CLI
+    pub fn from_cert_path<P: Into<PathBuf>>(cert_path: P) -> Self {
+        DockerTls {
+            files: TlsFiles::CertPath(cert_path.into()),
+            verify: true,
+        }
+    }
+
+    // the files are read when connecting
+    pub fn from_files<P: Into<PathBuf>>(ca: P, cert: P, key: P) -> Self {
+        DockerTls {
+            files: TlsFiles::Pem {
+                ca: ca.into(),
+                cert: cert.into(),
+                key: key.into(),
+            },
+            verify: true,
+        }
+    }
+
+    pub fn without_verification(mut self) -> Self {
+        self.verify = false;
+        self
+    }
+
+    fn from_config(config: &Config) -> Option<Self> {
+        if !config.docker_tls_verify() {
+            return None;
+        }
+        let cert_path = match config.docker_cert_path() {
+            Some(cert_path) => PathBuf::from(cert_path),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".docker"),
+        };
+        Some(Self::from_cert_path(cert_path))
     }
 }
 
@@ -109,26 +630,135 @@ impl Display for Loggable {
     }
 }
 
-pub(crate) struct ContainerClient {
+pub struct ContainerClient {
+    docker: Docker,
+    docker_host: Option<String>,
     inner_container: Container,
-    pub(crate) running_state: RwLock<Option<RunningState>>,
+    reused: bool,
 }
 
 impl ContainerClient {
-    fn new(container: Container) -> Self {
+    pub fn new(docker: Docker, docker_host: Option<String>, container: Container) -> Self {
         ContainerClient {
+            docker,
+            docker_host,
             inner_container: container,
-            running_state: RwLock::new(None),
+            reused: false,
         }
     }
 
-    pub async fn health_state(&self) -> Result<Option<String>, docker_api::Error> {
+    pub fn reused(docker: Docker, docker_host: Option<String>, container: Container) -> Self {
+        ContainerClient {
+            reused: true,
+            ..Self::new(docker, docker_host, container)
+        }
+    }
+
+    pub(crate) fn host(&self) -> String {
+        resolve_host(self.docker_host.as_deref())
+    }
+
+    // another handle on the same container, e.g. to remove it once the original is moved away
+    pub(crate) fn duplicate(&self) -> Self {
+        ContainerClient {
+            docker: self.docker.clone(),
+            docker_host: self.docker_host.clone(),
+            inner_container: Container::new(self.docker.clone(), self.inner_container.id().clone()),
+            reused: self.reused,
+        }
+    }
+
+    pub(crate) fn is_reused(&self) -> bool {
+        self.reused
+    }
+
+    pub(crate) fn into_stopped(self) -> Self {
+        ContainerClient {
+            reused: false,
+            ..self
+        }
+    }
+
+    pub async fn health_state(&self) -> Result<Option<String>, TestcontainersError> {
         let inspect = self.inner_container.inspect().await?;
         Ok(inspect.state.and_then(|state| state.health?.status))
     }
 
-    pub async fn logs(&self) -> Result<String, docker_api::Error> {
-        let opts = LogsOpts::builder().stdout(true).stderr(true).all().build();
+    // the os/architecture of the image actually used, which differs from the host under emulation
+    pub async fn platform(&self) -> Result<Option<String>, TestcontainersError> {
+        let Some(image) = self.inner_container.inspect().await?.image else {
+            return Ok(None);
+        };
+        let inspect = self.docker.images().get(image).inspect().await?;
+        Ok(inspect
+            .os
+            .zip(inspect.architecture)
+            .map(|(os, architecture)| format!("{os}/{architecture}")))
+    }
+
+    pub(crate) async fn commit(
+        &self,
+        repository: &str,
+        tag: Option<&str>,
+        excluded_labels: &[&str],
+    ) -> Result<String, TestcontainersError> {
+        let mut config = self.inner_container.inspect().await?.config;
+        if let Some(labels) = config.as_mut().and_then(|config| config.labels.as_mut()) {
+            labels.retain(|label, _| !excluded_labels.contains(&label.as_str()));
+        }
+        let mut opts = ContainerCommitOpts::builder().repo(repository);
+        if let Some(tag) = tag {
+            opts = opts.tag(tag);
+        }
+        Ok(self
+            .inner_container
+            .commit(&opts.build(), config.as_ref())
+            .await?)
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        self.inner_container.id().as_ref()
+    }
+
+    #[cfg(feature = "raw-api")]
+    pub(crate) fn docker(&self) -> &Docker {
+        &self.docker
+    }
+
+    #[cfg(feature = "raw-api")]
+    pub(crate) fn raw_container(&self) -> &Container {
+        &self.inner_container
+    }
+
+    pub(crate) async fn has_exited(&self) -> Result<bool, TestcontainersError> {
+        let inspect = self.inner_container.inspect().await?;
+        let status = inspect.state.and_then(|state| state.status);
+        Ok(status.as_deref() == Some("exited"))
+    }
+
+    pub(crate) async fn wait_for_exit(&self) -> Result<i64, TestcontainersError> {
+        Ok(self.inner_container.wait().await?.status_code)
+    }
+
+    pub(crate) async fn inspect_raw(
+        &self,
+    ) -> Result<ContainerInspect200Response, TestcontainersError> {
+        Ok(self.inner_container.inspect().await?)
+    }
+
+    pub(crate) async fn inspect(&self) -> Result<RunningState, TestcontainersError> {
+        Ok(self.inner_container.inspect().await?.into())
+    }
+
+    pub async fn logs(&self) -> Result<String, TestcontainersError> {
+        self.logs_with(&LogOptions::default()).await
+    }
+
+    pub(crate) async fn logs_with(
+        &self,
+        options: &LogOptions,
+    ) -> Result<String, TestcontainersError> {
+        let opts: LogsOpts = options.into();
         let logs = self
             .inner_container
             .logs(&opts)
@@ -147,67 +777,447 @@ impl ContainerClient {
         Ok(String::from_utf8_lossy(&logs).to_string())
     }
 
+    pub(crate) fn log_stream(
+        &self,
+        options: &LogOptions,
+    ) -> BoxStream<'static, Result<String, TestcontainersError>> {
+        let container = Container::new(self.docker.clone(), self.inner_container.id().clone());
+        let opts: LogsOpts = options.into();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut logs = container.logs(&opts);
+            let mut stdout = Vec::new();
+            let mut stderr = Vec::new();
+            while let Some(chunk) = logs.next().await {
+                let (buffer, bytes) = match chunk {
+                    Ok(TtyChunk::StdOut(bytes)) => (&mut stdout, bytes),
+                    Ok(TtyChunk::StdErr(bytes)) => (&mut stderr, bytes),
+                    Ok(TtyChunk::StdIn(_)) => continue,
+                    Err(error) => {
+                        let _ = sender.send(Err(error.into()));
+                        return;
+                    }
+                };
+                buffer.extend_from_slice(&bytes);
+                while let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=end).collect();
+                    let line = String::from_utf8_lossy(&line[..end]).to_string();
+                    if sender.send(Ok(line)).is_err() {
+                        return;
+                    }
+                }
+            }
+            for remaining in [stdout, stderr] {
+                if !remaining.is_empty() {
+                    let _ = sender.send(Ok(String::from_utf8_lossy(&remaining).to_string()));
+                }
+            }
+        });
+        futures_util::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|line| (line, receiver))
+        })
+        .boxed()
+    }
+
+    pub(crate) async fn exec(&self, command: &[String]) -> Result<ExecResult, TestcontainersError> {
+        self.exec_with(command, &ExecOptions::default()).await
+    }
+
+    async fn start_exec(
+        &self,
+        command: &[String],
+        options: &ExecOptions,
+    ) -> Result<
+        (
+            Exec,
+            impl Stream<Item = Result<TtyChunk, docker_api::conn::Error>> + Send + 'static,
+        ),
+        docker_api::Error,
+    > {
+        let mut create_opts = ExecCreateOpts::builder()
+            .command(command)
+            .attach_stdout(true)
+            .attach_stderr(true)
+            .env(
+                options
+                    .environment_variables
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}")),
+            );
+        if let Some(user) = &options.user {
+            create_opts = create_opts.user(user);
+        }
+        if let Some(working_dir) = &options.working_dir {
+            create_opts = create_opts.working_dir(working_dir);
+        }
+        let exec = Exec::create(
+            self.docker.clone(),
+            self.inner_container.id(),
+            &create_opts.build(),
+        )
+        .await?;
+        let stream = exec.start(&ExecStartOpts::builder().build()).await?;
+        Ok((exec, stream))
+    }
+
+    pub(crate) async fn exec_with(
+        &self,
+        command: &[String],
+        options: &ExecOptions,
+    ) -> Result<ExecResult, TestcontainersError> {
+        let (exec, mut stream) = self.start_exec(command, options).await?;
+        let mut stdout = vec![];
+        let mut stderr = vec![];
+        while let Some(chunk) = stream.next().await {
+            match chunk? {
+                TtyChunk::StdOut(bytes) => stdout.extend(bytes),
+                TtyChunk::StdErr(bytes) => stderr.extend(bytes),
+                TtyChunk::StdIn(_) => {}
+            }
+        }
+        let exit_code = exec.inspect().await?.exit_code.unwrap_or_default();
+        Ok(ExecResult {
+            exit_code: exit_code as i64,
+            stdout: String::from_utf8_lossy(&stdout).to_string(),
+            stderr: String::from_utf8_lossy(&stderr).to_string(),
+        })
+    }
+
+    pub(crate) async fn exec_stream(
+        &self,
+        command: &[String],
+        options: &ExecOptions,
+    ) -> Result<BoxStream<'static, Result<ExecOutput, TestcontainersError>>, TestcontainersError>
+    {
+        let (_exec, stream) = self.start_exec(command, options).await?;
+        Ok(stream
+            .filter_map(|chunk| async move {
+                match chunk {
+                    Ok(TtyChunk::StdOut(bytes)) => Some(Ok(ExecOutput::StdOut(bytes))),
+                    Ok(TtyChunk::StdErr(bytes)) => Some(Ok(ExecOutput::StdErr(bytes))),
+                    Ok(TtyChunk::StdIn(_)) => None,
+                    Err(error) => Some(Err(error.into())),
+                }
+            })
+            .boxed())
+    }
+
+    pub(crate) async fn copy_file_into(
+        &self,
+        container_path: &str,
+        contents: &[u8],
+    ) -> Result<(), TestcontainersError> {
+        Ok(self
+            .inner_container
+            .copy_file_into(container_path, contents)
+            .await?)
+    }
+
+    pub(crate) async fn copy_archive_from(
+        &self,
+        container_path: &str,
+    ) -> Result<Vec<u8>, TestcontainersError> {
+        let chunks = self
+            .inner_container
+            .copy_from(container_path)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(chunks.concat())
+    }
+
+    pub(crate) async fn copy_file_from(
+        &self,
+        container_path: &str,
+    ) -> Result<Vec<u8>, TestcontainersError> {
+        let archive = self.copy_archive_from(container_path).await?;
+        let mut archive = tar::Archive::new(archive.as_slice());
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type().is_file() {
+                let mut contents = Vec::with_capacity(entry.size() as usize);
+                std::io::Read::read_to_end(&mut entry, &mut contents)?;
+                return Ok(contents);
+            }
+        }
+        Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{container_path} is not a regular file"),
+        )
+        .into())
+    }
+
     pub(crate) async fn start_and_wait(
         &self,
         ready_strategy: &ReadyStrategy,
         timeout: Duration,
-    ) -> Result<(), docker_api::Error> {
-        self.inner_container.start().await?;
-        ready_strategy.wait(self, timeout).await?;
-        let mut rw_state = self.running_state.write().unwrap();
-        let inspect = self.inner_container.inspect().await?;
-        let running_state: RunningState = inspect.into();
+        consecutive_successes: u32,
+    ) -> Result<RunningState, TestcontainersError> {
+        if !self.reused {
+            self.inner_container.start().await?;
+        }
+        if let Err(mut error) = ready_strategy
+            .wait(self, timeout, consecutive_successes)
+            .await
+        {
+            if let TestcontainersError::StartupTimeout { logs, .. }
+            | TestcontainersError::StartupExited { logs, .. }
+            | TestcontainersError::Unhealthy { logs, .. } = &mut error
+            {
+                let options = LogOptions::default().with_tail(STARTUP_FAILURE_LOG_LINES);
+                *logs = self.logs_with(&options).await.unwrap_or_default();
+            }
+            return Err(error);
+        }
+        let running_state = self.inspect().await?;
         println!("🐋 Container {} is ready", running_state.name);
-        *rw_state = Some(running_state);
-        Ok(())
+        Ok(running_state)
     }
 
-    pub(crate) async fn stop(&self) -> Result<(), docker_api::Error> {
-        self.inner_container
-            .stop(&ContainerStopOpts::builder().build())
-            .await?;
-        let mut rw_state = self.running_state.write().unwrap();
-        let name = rw_state.clone().map_or("???".to_string(), |s| s.name);
-        *rw_state = None;
-        println!("🐋 Container {} is stopped", &name);
-        Ok(())
+    // docker defaults to the stop signal of the image and a 10 seconds grace period
+    pub(crate) async fn stop(
+        &self,
+        signal: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<(), TestcontainersError> {
+        let mut opts = ContainerStopOpts::builder();
+        if let Some(signal) = signal {
+            opts = opts.signal(signal);
+        }
+        if let Some(timeout) = timeout {
+            opts = opts.wait(timeout);
+        }
+        Ok(self.inner_container.stop(&opts.build()).await?)
     }
 
-    pub(crate) async fn kill(&self) -> Result<(), docker_api::Error> {
-        self.inner_container
+    pub(crate) async fn kill(&self) -> Result<(), TestcontainersError> {
+        Ok(self
+            .inner_container
             .stop(&ContainerStopOpts::builder().signal("SIGKILL").build())
+            .await?)
+    }
+
+    pub(crate) async fn remove(&self) -> Result<(), TestcontainersError> {
+        self.inner_container
+            .remove(
+                &ContainerRemoveOpts::builder()
+                    .force(true)
+                    .volumes(true)
+                    .build(),
+            )
             .await?;
-        let mut rw_state = self.running_state.write().unwrap();
-        let name = rw_state.clone().map_or("???".to_string(), |s| s.name);
-        *rw_state = None;
-        println!("🐋 Container {} killed", &name);
+        println!("🐋 Container {} removed", self.inner_container.id());
         Ok(())
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct LogOptions {
+    stdout: bool,
+    stderr: bool,
+    timestamps: bool,
+    tail: Option<usize>,
+    since: Option<SystemTime>,
+    follow: bool,
+}
+
+impl LogOptions {
+    pub fn stdout() -> Self {
+        LogOptions {
+            stderr: false,
+            ..Default::default()
+        }
+    }
+
+    pub fn stderr() -> Self {
+        LogOptions {
+            stdout: false,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_timestamps(mut self) -> Self {
+        self.timestamps = true;
+        self
+    }
+
+    pub fn with_tail(mut self, lines: usize) -> Self {
+        self.tail = Some(lines);
+        self
+    }
+
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn with_follow(mut self) -> Self {
+        self.follow = true;
+        self
+    }
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        LogOptions {
+            stdout: true,
+            stderr: true,
+            timestamps: false,
+            tail: None,
+            since: None,
+            follow: false,
+        }
+    }
+}
+
+impl From<&LogOptions> for LogsOpts {
+    fn from(options: &LogOptions) -> Self {
+        let mut opts = LogsOpts::builder()
+            .stdout(options.stdout)
+            .stderr(options.stderr)
+            .timestamps(options.timestamps)
+            .follow(options.follow);
+        opts = match options.tail {
+            Some(lines) => opts.n_lines(lines),
+            None => opts.all(),
+        };
+        if let Some(since) = options.since {
+            opts = opts.since(&DateTime::<Utc>::from(since));
+        }
+        opts.build()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ExecOptions {
+    user: Option<String>,
+    working_dir: Option<String>,
+    environment_variables: HashMap<String, String>,
+}
+
+impl ExecOptions {
+    pub fn with_user<S: Into<String>>(mut self, user: S) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    pub fn with_working_dir<S: Into<String>>(mut self, working_dir: S) -> Self {
+        self.working_dir = Some(working_dir.into());
+        self
+    }
+
+    pub fn add_env<S: Into<String>>(mut self, key: S, value: S) -> Self {
+        self.environment_variables.insert(key.into(), value.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExecOutput {
+    StdOut(Vec<u8>),
+    StdErr(Vec<u8>),
+}
+
+#[derive(Clone, Debug)]
+pub struct ExecResult {
+    pub exit_code: i64,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExitResult {
+    pub exit_code: i64,
+    pub stdout: String,
+    pub stderr: String,
+}
+
 #[derive(Clone)]
 pub(crate) struct RunningState {
-    id: String,
-    name: String,
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) running: bool,
     pub(crate) ports: HashMap<String, u16>,
+    // every port known to the container, published or not
+    pub(crate) exposed_ports: HashSet<String>,
+    pub(crate) networks: HashMap<String, NetworkEndpoint>,
+    pub(crate) gateway: Option<String>,
+    // with the host network nothing is published, container ports are host ports
+    pub(crate) host_network: bool,
 }
+
+#[derive(Clone)]
+pub(crate) struct NetworkEndpoint {
+    pub(crate) ip_address: Option<String>,
+    pub(crate) gateway: Option<String>,
+}
+
 impl From<ContainerInspect200Response> for RunningState {
     fn from(inspect: ContainerInspect200Response) -> Self {
-        let ports = Self::extract_port_mapping(inspect.network_settings).unwrap_or(HashMap::new());
+        let network_settings = inspect.network_settings.as_ref();
+        let ports = Self::extract_port_mapping(network_settings).unwrap_or_default();
+        let exposed_ports = network_settings
+            .and_then(|settings| settings.ports.as_ref())
+            .map(|ports| ports.keys().cloned().collect())
+            .unwrap_or_default();
+        let networks = Self::extract_networks(network_settings).unwrap_or_default();
+        let gateway = network_settings
+            .and_then(|settings| settings.gateway.clone())
+            .filter(|gateway| !gateway.is_empty())
+            .or_else(|| {
+                networks
+                    .values()
+                    .find_map(|endpoint| endpoint.gateway.clone())
+            });
+        let host_network = inspect
+            .host_config
+            .as_ref()
+            .and_then(|host_config| host_config["NetworkMode"].as_str())
+            == Some("host");
 
         RunningState {
             id: inspect.id.expect("container should have an id"),
-            name: inspect.name.expect("container should have a name"),
+            name: inspect
+                .name
+                .map(|name| name.trim_start_matches('/').to_string())
+                .expect("container should have a name"),
+            running: inspect
+                .state
+                .and_then(|state| state.running)
+                .unwrap_or_default(),
             ports,
+            exposed_ports,
+            networks,
+            gateway,
+            host_network,
         }
     }
 }
 impl RunningState {
+    fn extract_networks(
+        network_settings: Option<&NetworkSettings>,
+    ) -> Option<HashMap<String, NetworkEndpoint>> {
+        let non_empty = |value: &Option<String>| value.clone().filter(|value| !value.is_empty());
+        let networks = network_settings?
+            .networks
+            .as_ref()?
+            .iter()
+            .map(|(name, endpoint)| {
+                let endpoint = NetworkEndpoint {
+                    ip_address: non_empty(&endpoint.ip_address),
+                    gateway: non_empty(&endpoint.gateway),
+                };
+                (name.clone(), endpoint)
+            })
+            .collect();
+        Some(networks)
+    }
+
     fn extract_port_mapping(
-        network_settings: Option<NetworkSettings>,
+        network_settings: Option<&NetworkSettings>,
     ) -> Option<HashMap<String, u16>> {
         let ports: HashMap<String, u16> = network_settings?
-            .ports?
+            .ports
+            .as_ref()?
             .iter()
             .filter_map(|(container_port_spec, host_ports)| match host_ports {
                 Some(host_ports) => {
@@ -229,7 +1239,7 @@ impl RunningState {
             })
             .flatten()
             .filter_map(|(container_port_spec, host_ip, host_port)| {
-                if host_ip == "0.0.0.0".to_string() {
+                if host_ip == "0.0.0.0" {
                     Some((container_port_spec.into(), host_port.parse().unwrap()))
                 } else {
                     None
@@ -239,3 +1249,125 @@ impl RunningState {
         Some(ports)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::Ulimit;
+
+    #[test]
+    fn can_read_default_gateway() {
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+            eth0\t00000000\t010011AC\t0003\t0\t0\t0\t00000000\n\
+            eth0\t000011AC\t00000000\t0001\t0\t0\t0\t0000FFFF\n";
+        assert_eq!(default_gateway(routes), Some("172.17.0.1".to_string()));
+    }
+
+    #[test]
+    fn cant_read_missing_default_gateway() {
+        let routes = "Iface\tDestination\tGateway \tFlags\n\
+            eth0\t000011AC\t00000000\t0001\n";
+        assert_eq!(default_gateway(routes), None);
+    }
+
+    #[tokio::test]
+    async fn can_connect_to_tcp_docker_host() {
+        let docker = DockerClient::connect(Some("tcp://remote:2375"), None).unwrap();
+        assert_eq!(docker.docker_host(), Some("tcp://remote:2375"));
+        assert!(DockerClient::connect(Some("ftp://remote:2375"), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn injected_clients_name_the_settings_requiring_the_engine_api() {
+        let docker = DockerClient::from(Docker::unix("/nonexistent.sock"));
+        let engine_opts = EngineCreateOpts {
+            tmpfs: HashMap::from([("/tmp".to_string(), "rw".to_string())]),
+            ulimits: vec![Ulimit {
+                name: "nofile".to_string(),
+                soft: 1024,
+                hard: 1024,
+            }],
+            ..EngineCreateOpts::default()
+        };
+        let error = docker
+            .create(&ContainerCreateOpts::builder().build(), &engine_opts)
+            .await
+            .err()
+            .unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("with_tmpfs, with_ulimit requires"));
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn tls_files_are_read_when_connecting() {
+        let tls = DockerTls::from_files("/missing/ca.pem", "/missing/cert.pem", "/missing/key.pem");
+        assert!(DockerClient::connect(Some("tcp://remote:2376"), Some(&tls)).is_err());
+    }
+
+    #[cfg(not(feature = "tls"))]
+    #[test]
+    fn cant_connect_over_tls_without_tls_feature() {
+        let tls = DockerTls::from_cert_path("/certs");
+        assert!(DockerClient::connect(Some("tcp://remote:2376"), Some(&tls)).is_err());
+    }
+
+    #[test]
+    fn can_list_docker_socket_candidates() {
+        let candidates = docker_socket_candidates(
+            Some(Path::new("/home/me")),
+            Some(Path::new("/run/user/1000")),
+        );
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/var/run/docker.sock"),
+                PathBuf::from("/run/user/1000/docker.sock"),
+                PathBuf::from("/home/me/.docker/run/docker.sock"),
+                PathBuf::from("/home/me/.docker/desktop/docker.sock"),
+                PathBuf::from("/home/me/.colima/default/docker.sock"),
+                PathBuf::from("/home/me/.colima/docker.sock"),
+                PathBuf::from("/run/user/1000/podman/podman.sock"),
+                PathBuf::from("/run/podman/podman.sock"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reaper_mounts_the_socket_seen_by_the_daemon() {
+        let runtime_dir = Some(Path::new("/run/user/1000"));
+        for (socket, expected) in [
+            (None, "/var/run/docker.sock"),
+            (Some("/var/run/docker.sock"), "/var/run/docker.sock"),
+            (
+                Some("/run/user/1000/docker.sock"),
+                "/run/user/1000/docker.sock",
+            ),
+            (
+                Some("/run/user/1000/podman/podman.sock"),
+                "/run/user/1000/podman/podman.sock",
+            ),
+            (Some("/run/podman/podman.sock"), "/run/podman/podman.sock"),
+            (
+                Some("/home/me/.docker/run/docker.sock"),
+                "/var/run/docker.sock",
+            ),
+            (Some("/home/me/.colima/docker.sock"), "/var/run/docker.sock"),
+        ] {
+            assert_eq!(
+                daemon_side_socket(socket.map(Path::new), runtime_dir),
+                expected
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_client_is_initialized_once() {
+        assert!(Arc::ptr_eq(
+            &DockerClient::shared(),
+            &DockerClient::shared()
+        ));
+        assert!(DockerClient::set_shared(DockerClient::default()).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    auth,
+    image::BuildImageInstructions,
+    runtime::{EngineCreateOpts, Gpus},
+};
+use containers_api::{
+    conn::{
+        get_http_connector, get_unix_connector,
+        hyper::{body, Body, Client, Response},
+        Headers, Payload, RequestClient, Transport, AUTH_HEADER,
+    },
+    url::{construct_ep, encoded_pairs, url::Url},
+};
+use docker_api::{
+    models::{ContainerCreateResponse, ImageBuildChunk},
+    opts::{ContainerCreateOpts, RegistryAuth},
+};
+use futures_util::{Future, Stream, TryFutureExt};
+use serde::Deserialize;
+use serde_json::json;
+use std::{collections::HashMap, path::PathBuf, pin::Pin};
+
+const REGISTRY_CONFIG_HEADER: &str = "X-Registry-Config";
+
+// raw access to the docker engine API for the parameters docker-api has no option for
+#[derive(Clone, Debug)]
+pub(crate) struct EngineClient {
+    client: RequestClient<docker_api::Error>,
+}
+
+impl EngineClient {
+    pub(crate) fn new(docker_host: &str) -> Result<Self, docker_api::Error> {
+        match docker_host.split_once("://") {
+            Some(("unix", path)) => Ok(Self::unix(path)),
+            Some(("tcp" | "http", address)) => Self::tcp(address),
+            Some((scheme, _)) => Err(docker_api::Error::UnsupportedScheme(scheme.to_string())),
+            None => Err(docker_api::Error::MissingAuthority),
+        }
+    }
+
+    pub(crate) fn unix<P: Into<PathBuf>>(socket_path: P) -> Self {
+        Self::with_transport(Transport::Unix {
+            client: Client::builder()
+                .pool_max_idle_per_host(0)
+                .build(get_unix_connector()),
+            path: socket_path.into(),
+        })
+    }
+
+    pub(crate) fn tcp(address: &str) -> Result<Self, docker_api::Error> {
+        let host =
+            Url::parse(&format!("http://{address}")).map_err(docker_api::Error::InvalidUrl)?;
+        Ok(Self::with_transport(Transport::Tcp {
+            client: Client::builder().build(get_http_connector()),
+            host,
+        }))
+    }
+
+    #[cfg(feature = "tls")]
+    pub(crate) fn tls(
+        address: &str,
+        cert_path: &std::path::Path,
+        verify: bool,
+    ) -> Result<Self, docker_api::Error> {
+        let host =
+            Url::parse(&format!("https://{address}")).map_err(docker_api::Error::InvalidUrl)?;
+        let connector = containers_api::conn::get_https_connector(cert_path, verify)?;
+        Ok(Self::with_transport(Transport::EncryptedTcp {
+            client: Client::builder().build(connector),
+            host,
+        }))
+    }
+
+    fn with_transport(transport: Transport) -> Self {
+        EngineClient {
+            client: RequestClient::new(transport, Box::new(validate_response)),
+        }
+    }
+
+    pub(crate) fn pull<'a>(
+        &'a self,
+        image: &str,
+        platform: &str,
+        auth: Option<&RegistryAuth>,
+    ) -> impl Stream<Item = Result<ImageBuildChunk, docker_api::Error>> + Unpin + 'a {
+        let query = encoded_pairs([("fromImage", image), ("platform", platform)]);
+        let headers = auth.map(|auth| Headers::single(AUTH_HEADER, auth.serialize()));
+        Box::pin(self.client.post_into_stream(
+            construct_ep("/images/create", Some(query)),
+            Payload::empty(),
+            headers,
+        ))
+    }
+
+    pub(crate) fn build<'a>(
+        &'a self,
+        tag: &str,
+        build: &BuildImageInstructions,
+        platform: Option<&str>,
+        auths: &HashMap<String, RegistryAuth>,
+    ) -> impl Stream<Item = Result<ImageBuildChunk, docker_api::Error>> + Unpin + 'a {
+        let mut query = vec![("t", tag.to_string())];
+        if let Some(dockerfile) = &build.dockerfile {
+            query.push(("dockerfile", dockerfile.clone()));
+        }
+        if let Some(target) = &build.target {
+            query.push(("target", target.clone()));
+        }
+        if !build.build_args.is_empty() {
+            query.push(("buildargs", json!(build.build_args).to_string()));
+        }
+        if !build.labels.is_empty() {
+            query.push(("labels", json!(build.labels).to_string()));
+        }
+        if build.no_cache {
+            query.push(("nocache", "true".to_string()));
+        }
+        if build.pull {
+            query.push(("pull", "true".to_string()));
+        }
+        if let Some(platform) = platform {
+            query.push(("platform", platform.to_string()));
+        }
+        let endpoint = construct_ep("/build", Some(encoded_pairs(query)));
+        let context = build.context_archive();
+        let headers = (!auths.is_empty())
+            .then(|| Headers::single(REGISTRY_CONFIG_HEADER, auth::registry_config(auths)));
+        Box::pin(
+            async move {
+                Ok::<_, docker_api::Error>(self.client.post_into_stream(
+                    endpoint,
+                    Payload::Tar(context?),
+                    headers,
+                ))
+            }
+            .try_flatten_stream(),
+        )
+    }
+
+    pub(crate) async fn create_container(
+        &self,
+        opts: &ContainerCreateOpts,
+        engine_opts: &EngineCreateOpts,
+    ) -> Result<String, docker_api::Error> {
+        let params: Vec<_> = [
+            ("name", &engine_opts.name),
+            ("platform", &engine_opts.platform),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value.as_ref()?)))
+        .collect();
+        let query = (!params.is_empty()).then(|| encoded_pairs(&params));
+        let mut body: serde_json::Value = serde_json::from_slice(&opts.serialize_vec()?)?;
+        if let Some(gpus) = &engine_opts.gpus {
+            let (count, device_ids) = match gpus {
+                Gpus::All => (-1, vec![]),
+                Gpus::Count(count) => (i64::from(*count), vec![]),
+                Gpus::DeviceIds(device_ids) => (0, device_ids.clone()),
+            };
+            body["HostConfig"]["DeviceRequests"] = json!([{
+                "Driver": "",
+                "Count": count,
+                "DeviceIDs": device_ids,
+                "Capabilities": [["gpu"]],
+            }]);
+        }
+        if !engine_opts.device_cgroup_rules.is_empty() {
+            body["HostConfig"]["DeviceCgroupRules"] = json!(engine_opts.device_cgroup_rules);
+        }
+        if !engine_opts.tmpfs.is_empty() {
+            body["HostConfig"]["Tmpfs"] = json!(engine_opts.tmpfs);
+        }
+        if !engine_opts.ulimits.is_empty() {
+            body["HostConfig"]["Ulimits"] = engine_opts
+                .ulimits
+                .iter()
+                .map(
+                    |ulimit| json!({"Name": ulimit.name, "Soft": ulimit.soft, "Hard": ulimit.hard}),
+                )
+                .collect();
+        }
+        if !engine_opts.dns.is_empty() {
+            body["HostConfig"]["Dns"] = json!(engine_opts.dns);
+        }
+        if !engine_opts.dns_search.is_empty() {
+            body["HostConfig"]["DnsSearch"] = json!(engine_opts.dns_search);
+        }
+        if !engine_opts.dns_options.is_empty() {
+            body["HostConfig"]["DnsOptions"] = json!(engine_opts.dns_options);
+        }
+        for (key, mode) in [
+            ("PidMode", &engine_opts.pid_mode),
+            ("IpcMode", &engine_opts.ipc_mode),
+            ("UTSMode", &engine_opts.uts_mode),
+        ] {
+            if let Some(mode) = mode {
+                body["HostConfig"][key] = json!(mode);
+            }
+        }
+        if engine_opts.init {
+            body["HostConfig"]["Init"] = json!(true);
+        }
+        if engine_opts.oom_kill_disable {
+            body["HostConfig"]["OomKillDisable"] = json!(true);
+        }
+        if let Some(oom_score_adj) = engine_opts.oom_score_adj {
+            body["HostConfig"]["OomScoreAdj"] = json!(oom_score_adj);
+        }
+        if !engine_opts.group_add.is_empty() {
+            body["HostConfig"]["GroupAdd"] = json!(engine_opts.group_add);
+        }
+        let response: ContainerCreateResponse = self
+            .client
+            .post_json(
+                construct_ep("/containers/create", query),
+                Payload::Json(body.to_string()),
+                Headers::none(),
+            )
+            .await?;
+        Ok(response.id)
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+fn validate_response(
+    response: Response<Body>,
+) -> Pin<Box<dyn Future<Output = Result<Response<Body>, docker_api::Error>> + Send + Sync>> {
+    Box::pin(async move {
+        let status = response.status();
+        if status.is_success() || status.is_informational() {
+            return Ok(response);
+        }
+        let bytes = body::to_bytes(response.into_body())
+            .await
+            .map_err(containers_api::conn::Error::from)?;
+        let message = serde_json::from_slice::<ErrorResponse>(&bytes)
+            .map(|error| error.message)
+            .unwrap_or_else(|_| String::from_utf8_lossy(&bytes).to_string());
+        Err(docker_api::Error::Fault {
+            code: status,
+            message,
+        })
+    })
+}
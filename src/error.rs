@@ -0,0 +1,69 @@
+use crate::port::PortError;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TestcontainersError {
+    #[error("invalid container configuration: {0}")]
+    InvalidConfiguration(String),
+    #[error("image {image} is not available locally and the pull policy forbids pulling it")]
+    ImageNotFound { image: String },
+    #[error("failed to pull image {image}: {source}")]
+    ImagePull {
+        image: String,
+        source: docker_api::Error,
+    },
+    #[error("failed to build image {image}: {source}")]
+    ImageBuild {
+        image: String,
+        source: docker_api::Error,
+    },
+    #[error("failed to load image archive {archive}: {source}")]
+    ImageLoad {
+        archive: String,
+        source: docker_api::Error,
+    },
+    #[error("failed to create container from image {image}: {source}")]
+    ContainerCreate {
+        image: String,
+        source: docker_api::Error,
+    },
+    #[error("container {container_id} takes too much time to be ready: {strategy} not satisfied after {elapsed:?}\n🐋 Last log lines:\n{logs}")]
+    StartupTimeout {
+        container_id: String,
+        strategy: String,
+        elapsed: Duration,
+        logs: String,
+    },
+    #[error("container {container_id} exited before being ready: {strategy} not satisfied after {elapsed:?}\n🐋 Last log lines:\n{logs}")]
+    StartupExited {
+        container_id: String,
+        strategy: String,
+        elapsed: Duration,
+        logs: String,
+    },
+    #[error("container {container_id} health check reported an unhealthy state\n🐋 Last log lines:\n{logs}")]
+    Unhealthy { container_id: String, logs: String },
+    #[error("unsatisfiable or cyclic dependencies for containers: {}", .0.join(", "))]
+    UnsatisfiableDependencies(Vec<String>),
+    #[error("http request failure: {0}")]
+    Http(String),
+    #[error("container provisioning failure: {0}")]
+    Provisioning(String),
+    #[error("docker compose failure: {0}")]
+    Compose(String),
+    #[error("resource reaper failure: {0}")]
+    Reaper(String),
+    #[error(transparent)]
+    Port(#[from] PortError),
+    #[error(transparent)]
+    Docker(#[from] docker_api::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl From<docker_api::conn::Error> for TestcontainersError {
+    fn from(error: docker_api::conn::Error) -> Self {
+        TestcontainersError::Docker(error.into())
+    }
+}
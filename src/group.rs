@@ -0,0 +1,168 @@
+use crate::{
+    container::{GenericContainerBuilder, RunningContainer},
+    error::TestcontainersError,
+};
+use futures_util::future::join_all;
+use log::warn;
+
+#[derive(Clone, Default)]
+pub struct ContainerGroup {
+    builders: Vec<(String, GenericContainerBuilder)>,
+}
+
+impl ContainerGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add<S: Into<String>>(mut self, name: S, builder: GenericContainerBuilder) -> Self {
+        self.builders.push((name.into(), builder));
+        self
+    }
+
+    pub async fn start(self) -> Result<RunningContainerGroup, TestcontainersError> {
+        let mut group = RunningContainerGroup { containers: vec![] };
+        let mut pending = self.builders;
+        // containers are started wave by wave, each wave holding every container whose
+        // dependencies are already running
+        while !pending.is_empty() {
+            let (wave, waiting): (Vec<_>, Vec<_>) =
+                pending.into_iter().partition(|(_, builder)| {
+                    builder
+                        .dependencies
+                        .iter()
+                        .all(|dependency| group.get_by_id(dependency.id).is_some())
+                });
+            pending = waiting;
+            if wave.is_empty() {
+                let names = pending.iter().map(|(name, _)| name.clone()).collect();
+                group.discard().await;
+                return Err(TestcontainersError::UnsatisfiableDependencies(names));
+            }
+
+            let results = join_all(
+                wave.into_iter()
+                    .map(|(name, builder)| group.start_member(name, builder)),
+            )
+            .await;
+            let mut first_error = None;
+            for result in results {
+                match result {
+                    Ok(container) => group.containers.push(container),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                }
+            }
+            if let Some(error) = first_error {
+                group.discard().await;
+                return Err(error);
+            }
+        }
+        Ok(group)
+    }
+}
+
+pub struct RunningContainerGroup {
+    containers: Vec<(String, u64, RunningContainer)>,
+}
+
+impl RunningContainerGroup {
+    async fn start_member(
+        &self,
+        name: String,
+        builder: GenericContainerBuilder,
+    ) -> Result<(String, u64, RunningContainer), TestcontainersError> {
+        for dependency in &builder.dependencies {
+            if let (Some(condition), Some(container)) =
+                (&dependency.condition, self.get_by_id(dependency.id))
+            {
+                container.wait_until(condition).await?;
+            }
+        }
+        let id = builder.id;
+        let container = builder.create().await?;
+        // a container that fails to start is not part of the group yet, it is removed right away
+        let handle = container.client_handle();
+        match container.start().await {
+            Ok(container) => Ok((name, id, container)),
+            Err(error) => {
+                if let Err(cleanup_error) = handle.remove().await {
+                    warn!("🐋 Unable to remove container {name} of the group: {cleanup_error}");
+                }
+                Err(error)
+            }
+        }
+    }
+
+    // cleanup after a failed start, the startup error matters more than a cleanup error
+    async fn discard(self) {
+        let results = join_all(
+            self.containers
+                .into_iter()
+                .map(
+                    |(name, _, container)| async move { (name, container.stop_and_remove().await) },
+                ),
+        )
+        .await;
+        for (name, result) in results {
+            if let Err(error) = result {
+                warn!("🐋 Unable to remove container {name} of the group: {error}");
+            }
+        }
+    }
+
+    fn get_by_id(&self, id: u64) -> Option<&RunningContainer> {
+        self.containers
+            .iter()
+            .find(|(_, container_id, _)| *container_id == id)
+            .map(|(_, _, container)| container)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RunningContainer> {
+        self.containers
+            .iter()
+            .find(|(container_name, _, _)| container_name == name)
+            .map(|(_, _, container)| container)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &RunningContainer)> {
+        self.containers
+            .iter()
+            .map(|(name, _, container)| (name.as_str(), container))
+    }
+
+    pub async fn stop(self) -> Result<(), TestcontainersError> {
+        let results = join_all(
+            self.containers
+                .into_iter()
+                .map(|(_, _, container)| container.stop()),
+        )
+        .await;
+        results
+            .into_iter()
+            .try_for_each(|result| result.map(|_| ()))
+    }
+
+    pub async fn kill(self) -> Result<(), TestcontainersError> {
+        let results = join_all(
+            self.containers
+                .into_iter()
+                .map(|(_, _, container)| container.kill()),
+        )
+        .await;
+        results
+            .into_iter()
+            .try_for_each(|result| result.map(|_| ()))
+    }
+
+    pub async fn stop_and_remove(self) -> Result<(), TestcontainersError> {
+        let results = join_all(
+            self.containers
+                .into_iter()
+                .map(|(_, _, container)| container.stop_and_remove()),
+        )
+        .await;
+        results.into_iter().collect()
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    error::TestcontainersError,
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hyper::{
+    body,
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Body, Client, Method, Request, StatusCode,
+};
+
+// a minimal client for the HTTP APIs modules are provisioned and probed through
+pub(crate) struct HttpRequest {
+    method: Method,
+    url: String,
+    basic_auth: Option<(String, String)>,
+    body: Option<(&'static str, Vec<u8>)>,
+}
+
+impl HttpRequest {
+    pub(crate) fn new<S: Into<String>>(method: Method, url: S) -> Self {
+        HttpRequest {
+            method,
+            url: url.into(),
+            basic_auth: None,
+            body: None,
+        }
+    }
+
+    pub(crate) fn with_basic_auth(mut self, username: &str, password: &str) -> Self {
+        self.basic_auth = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    pub(crate) fn with_json(mut self, json: serde_json::Value) -> Self {
+        self.body = Some(("application/json", json.to_string().into_bytes()));
+        self
+    }
+
+    pub(crate) fn with_text<S: Into<String>>(mut self, text: S) -> Self {
+        self.body = Some(("text/plain", text.into().into_bytes()));
+        self
+    }
+
+    pub(crate) async fn send(self) -> Result<(StatusCode, String), TestcontainersError> {
+        let mut request = Request::builder().method(self.method).uri(&self.url);
+        if let Some((username, password)) = &self.basic_auth {
+            let credentials = STANDARD.encode(format!("{username}:{password}"));
+            request = request.header(AUTHORIZATION, format!("Basic {credentials}"));
+        }
+        let body = match self.body {
+            Some((content_type, body)) => {
+                request = request.header(CONTENT_TYPE, content_type);
+                Body::from(body)
+            }
+            None => Body::empty(),
+        };
+        let request = request
+            .body(body)
+            .map_err(|error| http_error(&self.url, error))?;
+        let response = Client::new()
+            .request(request)
+            .await
+            .map_err(|error| http_error(&self.url, error))?;
+        let status = response.status();
+        let body = body::to_bytes(response.into_body())
+            .await
+            .map_err(|error| http_error(&self.url, error))?;
+        Ok((status, String::from_utf8_lossy(&body).to_string()))
+    }
+
+    // any non 2xx response is an error
+    pub(crate) async fn send_expecting_success(self) -> Result<String, TestcontainersError> {
+        let method = self.method.clone();
+        let url = self.url.clone();
+        match self.send().await? {
+            (status, body) if status.is_success() => Ok(body),
+            (status, body) => Err(TestcontainersError::Http(format!(
+                "{method} {url} responded {status}: {body}"
+            ))),
+        }
+    }
+}
+
+fn http_error<E: std::fmt::Display>(url: &str, error: E) -> TestcontainersError {
+    TestcontainersError::Http(format!("{url}: {error}"))
+}
+
+// ready once a GET on the mapped port answers with a 2xx status
+pub(crate) struct HttpGetSucceeds {
+    port: u16,
+    path: String,
+}
+
+impl HttpGetSucceeds {
+    pub(crate) fn new<S: Into<String>>(port: u16, path: S) -> Self {
+        HttpGetSucceeds {
+            port,
+            path: path.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl WaitStrategy for HttpGetSucceeds {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container
+            .get_host_port(format!("{}/tcp", self.port))
+            .await?
+        else {
+            return Ok(false);
+        };
+        let url = format!("http://{}:{port}{}", container.host(), self.path);
+        let response = HttpRequest::new(Method::GET, url).send().await;
+        Ok(matches!(response, Ok((status, _)) if status.is_success()))
+    }
+
+    fn describe(&self) -> String {
+        format!("GET {} on port {} succeeds", self.path, self.port)
+    }
+}
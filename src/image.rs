@@ -1,11 +1,76 @@
 use std::{
-    fmt::{format, Display},
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    fmt::Display,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
     str::FromStr,
+    sync::OnceLock,
+    time::Duration,
 };
 
-use docker_api::opts::{ImageBuildOpts, ImageFilter};
+use crate::{auth::DOCKER_HUB_ADDRESS, config::Config, error::TestcontainersError};
+
+use docker_api::opts::ImageFilter;
+use log::info;
 use regex::Regex;
 
+static IMAGE_NAME_SUBSTITUTOR: OnceLock<Box<dyn ImageNameSubstitutor>> = OnceLock::new();
+
+// rewrites image names before they are pulled, e.g. to go through a corporate mirror
+pub trait ImageNameSubstitutor: Send + Sync {
+    fn substitute(&self, image: DockerImage) -> DockerImage;
+}
+
+impl<F> ImageNameSubstitutor for F
+where
+    F: Fn(DockerImage) -> DockerImage + Send + Sync,
+{
+    fn substitute(&self, image: DockerImage) -> DockerImage {
+        self(image)
+    }
+}
+
+// only effective before the first container is declared
+pub fn set_image_name_substitutor<S: ImageNameSubstitutor + 'static>(
+    substitutor: S,
+) -> Result<(), TestcontainersError> {
+    IMAGE_NAME_SUBSTITUTOR
+        .set(Box::new(substitutor))
+        .map_err(|_| {
+            TestcontainersError::InvalidConfiguration(
+                "the image name substitutor is already initialized".to_string(),
+            )
+        })
+}
+
+// the configured hub prefix applies first, then the custom substitutor
+pub(crate) fn substitute(image: DockerImage) -> DockerImage {
+    apply_substitution(
+        image,
+        Config::global().hub_image_name_prefix(),
+        IMAGE_NAME_SUBSTITUTOR
+            .get()
+            .map(|substitutor| substitutor.as_ref()),
+    )
+}
+
+fn apply_substitution(
+    image: DockerImage,
+    hub_prefix: Option<&str>,
+    substitutor: Option<&dyn ImageNameSubstitutor>,
+) -> DockerImage {
+    let original = image.clone();
+    let image = image.with_hub_prefix(hub_prefix);
+    let image = match substitutor {
+        Some(substitutor) => substitutor.substitute(image),
+        None => image,
+    };
+    if image != original {
+        info!("🐋 Using image {image} instead of {original}");
+    }
+    image
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DockerImage {
     raw_name: String,
@@ -31,6 +96,67 @@ impl DockerImage {
         }
     }
 
+    // built images are tagged after their build instructions so that identical builds share a tag
+    pub(crate) fn from_build(build_instructions: BuildImageInstructions) -> Self {
+        let mut hasher = DefaultHasher::new();
+        build_instructions.hash(&mut hasher);
+        let repository = format!("testcontainers/build-{:016x}", hasher.finish());
+        DockerImage {
+            build_instructions: Some(build_instructions),
+            ..DockerImage::new(
+                format!("{repository}:latest"),
+                None,
+                repository,
+                Version::Tag("latest".into()),
+            )
+        }
+    }
+
+    pub(crate) fn build_instructions(&self) -> Option<&BuildImageInstructions> {
+        self.build_instructions.as_ref()
+    }
+
+    pub(crate) fn unparsed<S: Into<String>>(raw_name: S) -> Self {
+        let raw_name = raw_name.into();
+        DockerImage::new(raw_name.clone(), None, raw_name, Version::Any)
+    }
+
+    // only images hosted on the docker hub go through the configured mirror
+    pub(crate) fn with_hub_prefix(self, prefix: Option<&str>) -> Self {
+        match prefix {
+            Some(prefix) if self.registry.is_none() && self.build_instructions.is_none() => {
+                format!("{prefix}{}", self.raw_name).parse().unwrap_or(self)
+            }
+            _ => self,
+        }
+    }
+
+    pub(crate) fn registry_address(&self) -> &str {
+        self.registry.as_deref().unwrap_or(DOCKER_HUB_ADDRESS)
+    }
+
+    pub fn registry(&self) -> Option<&str> {
+        self.registry.as_deref()
+    }
+
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    pub(crate) fn qualified_repository(&self) -> String {
+        match &self.registry {
+            Some(registry) => format!("{registry}/{}", self.repository),
+            None => self.repository.clone(),
+        }
+    }
+
+    pub(crate) fn tag(&self) -> Option<&str> {
+        match &self.version {
+            Version::Tag(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
     pub fn get_full_name(&self) -> String {
         self.raw_name.clone()
     }
@@ -70,37 +196,120 @@ impl FromStr for DockerImage {
         if repository.contains("@") || repository.contains(":") {
             Err(format!("invalid repository name: {repository}"))
         } else {
-            Ok(DockerImage {
-                raw_name: full_image_name.into(),
-                registry: registry.map(|r| r.into()),
-                repository: repository.into(),
+            Ok(DockerImage::new(
+                full_image_name,
+                registry,
+                repository,
                 version,
-                build_instructions: None,
-            })
+            ))
         }
     }
 }
 
-impl From<&str> for DockerImage {
-    fn from(full_image_name: &str) -> Self {
-        full_image_name
-            .parse()
-            .expect(format!("docker image name should be parseable: {full_image_name}").as_str())
+impl TryFrom<&str> for DockerImage {
+    type Error = String;
+
+    fn try_from(full_image_name: &str) -> Result<Self, Self::Error> {
+        full_image_name.parse()
+    }
+}
+
+impl TryFrom<String> for DockerImage {
+    type Error = String;
+
+    fn try_from(full_image_name: String) -> Result<Self, Self::Error> {
+        full_image_name.parse()
     }
 }
 
-impl Into<ImageFilter> for DockerImage {
-    fn into(self) -> ImageFilter {
-        let image = self
-            .registry
-            .map(|registry| format!("{registry}/{}", self.repository))
-            .unwrap_or(self.repository);
-        let tag = match self.version {
+impl From<DockerImage> for ImageFilter {
+    fn from(image: DockerImage) -> Self {
+        let tag = match &image.version {
             Version::Any => None,
-            Version::Sha256(sha256) => Some(sha256),
-            Version::Tag(tag) => Some(tag),
+            Version::Sha256(sha256) => Some(sha256.clone()),
+            Version::Tag(tag) => Some(tag.clone()),
         };
-        ImageFilter::Reference(image, tag)
+        ImageFilter::Reference(image.qualified_repository(), tag)
+    }
+}
+
+// an archive produced by docker save or an OCI image layout tarball, optionally gzipped
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImageArchive {
+    path: PathBuf,
+}
+
+impl ImageArchive {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        ImageArchive { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Display for ImageArchive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PullPolicy {
+    Always,
+    IfNotPresent,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PullRetry {
+    max_attempts: u32,
+    max_duration: Duration,
+    initial_backoff: Duration,
+}
+
+impl PullRetry {
+    pub fn new(max_attempts: u32, max_duration: Duration) -> Self {
+        PullRetry {
+            max_attempts,
+            max_duration,
+            ..Default::default()
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(1, Duration::ZERO)
+    }
+
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub(crate) fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub(crate) fn max_duration(&self) -> Duration {
+        self.max_duration
+    }
+
+    // exponential backoff capped to 30 seconds between attempts
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(Duration::from_secs(30))
+    }
+}
+
+impl Default for PullRetry {
+    fn default() -> Self {
+        PullRetry {
+            max_attempts: 3,
+            max_duration: Duration::from_secs(300),
+            initial_backoff: Duration::from_secs(1),
+        }
     }
 }
 
@@ -130,17 +339,106 @@ impl Version {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct BuildImageInstructions {
-    path: String,
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub(crate) struct BuildImageInstructions {
+    pub(crate) context_dir: Option<PathBuf>,
+    // added to the context, replacing files of the context directory with the same path
+    pub(crate) context_files: BTreeMap<String, Vec<u8>>,
+    pub(crate) dockerfile: Option<String>,
+    pub(crate) build_args: BTreeMap<String, String>,
+    pub(crate) target: Option<String>,
+    pub(crate) labels: BTreeMap<String, String>,
+    pub(crate) no_cache: bool,
+    pub(crate) pull: bool,
 }
 
-impl Into<Option<ImageBuildOpts>> for DockerImage {
-    fn into(self) -> Option<ImageBuildOpts> {
-        self.build_instructions.map(|i| {
-            let opts = ImageBuildOpts::builder(i.path);
-            opts.build()
-        })
+impl BuildImageInstructions {
+    pub(crate) fn from_context_dir(context_dir: PathBuf) -> Self {
+        BuildImageInstructions {
+            context_dir: Some(context_dir),
+            ..Self::in_memory()
+        }
+    }
+
+    pub(crate) fn from_dockerfile_content(dockerfile: String) -> Self {
+        let mut build = Self::in_memory();
+        build
+            .context_files
+            .insert("Dockerfile".to_string(), dockerfile.into_bytes());
+        build
+    }
+
+    fn in_memory() -> Self {
+        BuildImageInstructions {
+            context_dir: None,
+            context_files: BTreeMap::new(),
+            dockerfile: None,
+            build_args: BTreeMap::new(),
+            target: None,
+            labels: BTreeMap::new(),
+            no_cache: false,
+            pull: false,
+        }
+    }
+
+    // the registries of the FROM images, the daemon pulls them with the credentials sent along
+    // the build, images named with build args and earlier stages are left out
+    pub(crate) fn base_image_registries(&self) -> Vec<String> {
+        let dockerfile_name = self.dockerfile.as_deref().unwrap_or("Dockerfile");
+        let dockerfile = match self.context_files.get(dockerfile_name) {
+            Some(contents) => String::from_utf8_lossy(contents).to_string(),
+            None => self
+                .context_dir
+                .as_ref()
+                .and_then(|dir| std::fs::read_to_string(dir.join(dockerfile_name)).ok())
+                .unwrap_or_default(),
+        };
+        let mut stages = vec![];
+        let mut registries = vec![];
+        for line in dockerfile.lines() {
+            let mut words = line
+                .split_whitespace()
+                .filter(|word| !word.starts_with("--"));
+            if !words
+                .next()
+                .is_some_and(|word| word.eq_ignore_ascii_case("FROM"))
+            {
+                continue;
+            }
+            let Some(image) = words.next() else {
+                continue;
+            };
+            if !image.contains('$') && image != "scratch" && !stages.contains(&image) {
+                if let Ok(image) = image.parse::<DockerImage>() {
+                    let registry = image.registry_address().to_string();
+                    if !registries.contains(&registry) {
+                        registries.push(registry);
+                    }
+                }
+            }
+            if let (Some(as_keyword), Some(stage)) = (words.next(), words.next()) {
+                if as_keyword.eq_ignore_ascii_case("AS") {
+                    stages.push(stage);
+                }
+            }
+        }
+        registries
+    }
+
+    pub(crate) fn context_archive(&self) -> std::io::Result<Vec<u8>> {
+        let mut archive = tar::Builder::new(vec![]);
+        archive.follow_symlinks(false);
+        if let Some(context_dir) = &self.context_dir {
+            archive.append_dir_all(".", context_dir)?;
+        }
+        for (path, contents) in &self.context_files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            archive.append_data(&mut header, path, contents.as_slice())?;
+        }
+        archive.into_inner()
     }
 }
 
@@ -272,4 +570,106 @@ mod tests {
             Err("invalid tag version: rust:invalid".into())
         );
     }
+
+    #[test]
+    fn can_prefix_docker_hub_images() {
+        let prefixed = DockerImage::from_str("postgres:latest")
+            .unwrap()
+            .with_hub_prefix(Some("mirror.local/"));
+        assert_eq!(prefixed.get_full_name(), "mirror.local/postgres:latest");
+        let private = DockerImage::from_str("registry.foo.com/my-name:1.0")
+            .unwrap()
+            .with_hub_prefix(Some("mirror.local/"));
+        assert_eq!(private.get_full_name(), "registry.foo.com/my-name:1.0");
+    }
+
+    #[test]
+    fn custom_substitutor_applies_after_hub_prefix() {
+        let mirror = |image: DockerImage| match image.registry() {
+            None => format!("corp-mirror.example.com/library/{image}")
+                .parse()
+                .unwrap(),
+            Some(_) => image,
+        };
+        let image = DockerImage::from_str("postgres:latest").unwrap();
+        assert_eq!(
+            apply_substitution(image.clone(), None, Some(&mirror)).get_full_name(),
+            "corp-mirror.example.com/library/postgres:latest"
+        );
+        assert_eq!(
+            apply_substitution(image.clone(), Some("mirror.local/"), Some(&mirror)).get_full_name(),
+            "mirror.local/postgres:latest"
+        );
+        assert_eq!(apply_substitution(image.clone(), None, None), image);
+    }
+
+    #[test]
+    fn built_images_are_tagged_after_their_instructions() {
+        let build = BuildImageInstructions::from_context_dir(PathBuf::from("docker/app"));
+        let image = DockerImage::from_build(build.clone());
+        assert!(image.get_full_name().starts_with("testcontainers/build-"));
+        assert_eq!(image, DockerImage::from_build(build.clone()));
+        let mut other = build;
+        other.build_args.insert("VERSION".into(), "1.0".into());
+        assert_ne!(
+            image.get_full_name(),
+            DockerImage::from_build(other).get_full_name()
+        );
+    }
+
+    #[test]
+    fn in_memory_files_are_added_to_the_build_context() {
+        let mut build = BuildImageInstructions::from_dockerfile_content("FROM alpine".into());
+        build
+            .context_files
+            .insert("conf/app.conf".into(), b"debug=true".to_vec());
+        let context = build.context_archive().unwrap();
+        let mut files: Vec<_> = tar::Archive::new(context.as_slice())
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let mut contents = String::new();
+                std::io::Read::read_to_string(&mut entry, &mut contents).unwrap();
+                (entry.path().unwrap().display().to_string(), contents)
+            })
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                ("Dockerfile".to_string(), "FROM alpine".to_string()),
+                ("conf/app.conf".to_string(), "debug=true".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_pulls_from_registries_of_base_images() {
+        let build = BuildImageInstructions::from_dockerfile_content(
+            "FROM --platform=linux/amd64 registry.foo.com/base:1 AS builder\n\
+             RUN make\n\
+             FROM ${BASE_IMAGE}\n\
+             FROM alpine:3.19\n\
+             COPY --from=builder /app /app\n\
+             FROM builder\n"
+                .to_string(),
+        );
+        assert_eq!(
+            build.base_image_registries(),
+            vec![
+                "registry.foo.com".to_string(),
+                DOCKER_HUB_ADDRESS.to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn pull_retry_backoff_is_exponential_and_capped() {
+        let retry = PullRetry::default();
+        assert_eq!(retry.backoff(1), Duration::from_secs(1));
+        assert_eq!(retry.backoff(2), Duration::from_secs(2));
+        assert_eq!(retry.backoff(3), Duration::from_secs(4));
+        assert_eq!(retry.backoff(10), Duration::from_secs(30));
+    }
 }
@@ -1,15 +1,54 @@
-use container::{GenericContainer, GenericContainerBuilder};
+pub use testcontainers_macros::test;
 
+pub use modules::{
+    artemis::activemq_artemis,
+    consul::consul,
+    elasticsearch::{elasticsearch, elasticsearch_secured, opensearch},
+    etcd::etcd,
+    k3s::k3s,
+    kafka::kafka,
+    keycloak::{keycloak, keycloak_with_realm_import},
+    localstack::{localstack, localstack_with_services},
+    mailpit::mailpit,
+    minio::minio,
+    mongodb::{mongodb, mongodb_replica_set},
+    mysql::mysql,
+    nats::{nats, nats_with_jetstream},
+    nginx::{nginx, nginx_with_config, nginx_with_static_content},
+    ollama::ollama,
+    openldap::{openldap, openldap_with_ldif},
+    postgresql::{pgvector, postgresql, timescaledb},
+    pulsar::pulsar,
+    rabbitmq::rabbitmq,
+    redis::{redis, redis_stack},
+    scylla::scylla,
+    sftp::{sftp_server, sftp_server_with_key},
+    toxiproxy::toxiproxy,
+    trino::{trino, trino_with_catalogs},
+    wiremock::wiremock,
+};
+
+mod auth;
+pub mod blocking;
+pub mod compose;
+pub mod config;
 pub mod container;
+pub mod definition;
 pub mod docker_client;
+mod engine;
+pub mod error;
+pub mod group;
+mod http;
 pub mod image;
-
-pub async fn postgresql() -> GenericContainerBuilder {
-    GenericContainer::from_image("postgres:latest")
-        .add_env("POSTGRES_DB", "test")
-        .add_env("POSTGRES_USER", "test")
-        .add_env("POSTGRES_PASSWORD", "test")
-        .add_exposed_tcp_port(5432)
-        .with_command(&["postgres", "-c", "fsync=off"])
-        .wait_for_log_on_startup(r"(?s).*database system is ready to accept connections.*\s.*database system is ready to accept connections.*\s")
-}
+#[doc(hidden)]
+pub mod macro_support;
+pub mod modules;
+pub mod mount;
+pub mod network;
+pub mod port;
+pub mod reaper;
+pub mod runtime;
+pub mod session;
+pub mod shared;
+pub mod volume;
+pub mod wait;
@@ -0,0 +1,52 @@
+use crate::{
+    container::{GenericContainerBuilder, RunningContainer},
+    docker_client::ContainerClient,
+};
+use futures_util::FutureExt;
+use log::warn;
+use std::{any::Any, future::Future, panic::AssertUnwindSafe};
+
+// what the code generated by #[testcontainers::test] relies on, not meant to be used directly
+#[derive(Default)]
+pub struct ContainerCleanup {
+    containers: Vec<ContainerClient>,
+}
+
+impl ContainerCleanup {
+    pub async fn start(&mut self, builder: GenericContainerBuilder) -> RunningContainer {
+        let started = match builder.create().await {
+            Ok(container) => container.start().await,
+            Err(error) => Err(error),
+        };
+        match started {
+            Ok(container) => {
+                self.containers.push(container.client_handle());
+                container
+            }
+            Err(error) => {
+                self.remove_all().await;
+                panic!("🐋 Unable to start a test container: {error}");
+            }
+        }
+    }
+
+    // reused containers are left running for the next test run
+    pub async fn remove_all(&mut self) {
+        for container in self.containers.drain(..) {
+            if container.is_reused() {
+                continue;
+            }
+            if let Err(error) = container.remove().await {
+                warn!("🐋 Unable to remove test container: {error}");
+            }
+        }
+    }
+}
+
+pub async fn catch_unwind<F: Future>(future: F) -> Result<F::Output, Box<dyn Any + Send>> {
+    AssertUnwindSafe(future).catch_unwind().await
+}
+
+pub fn resume<T>(result: Result<T, Box<dyn Any + Send>>) -> T {
+    result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
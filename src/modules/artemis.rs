@@ -0,0 +1,99 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    http::HttpGetSucceeds,
+    modules::{env_vars, ContainerModule, ModuleBuilder},
+};
+use std::{ops::Deref, sync::Arc};
+
+// the acceptor on 61616 speaks both the core protocol and openwire
+const OPENWIRE_PORT: u16 = 61616;
+const AMQP_PORT: u16 = 5672;
+const STOMP_PORT: u16 = 61613;
+const CONSOLE_PORT: u16 = 8161;
+
+pub async fn activemq_artemis() -> GenericContainerBuilder {
+    ModuleBuilder::new(Artemis).into_builder()
+}
+
+pub struct Artemis;
+
+impl ContainerModule for Artemis {
+    type Container = ArtemisContainer;
+
+    fn image(&self) -> String {
+        "apache/activemq-artemis:2.33.0".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[("ARTEMIS_USER", "artemis"), ("ARTEMIS_PASSWORD", "artemis")])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![OPENWIRE_PORT, AMQP_PORT, STOMP_PORT, CONSOLE_PORT]
+    }
+
+    // the web console is the last component the broker starts
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(CONSOLE_PORT, "/console/")))
+    }
+}
+
+pub struct ArtemisContainer {
+    container: RunningContainer,
+}
+
+impl ArtemisContainer {
+    pub fn username(&self) -> &str {
+        self.container.get_env("ARTEMIS_USER").unwrap_or("artemis")
+    }
+
+    pub fn password(&self) -> &str {
+        self.container
+            .get_env("ARTEMIS_PASSWORD")
+            .unwrap_or("artemis")
+    }
+
+    pub fn broker_url(&self) -> String {
+        format!("tcp://{}", self.address(OPENWIRE_PORT))
+    }
+
+    pub fn amqp_url(&self) -> String {
+        format!("amqp://{}", self.address(AMQP_PORT))
+    }
+
+    pub fn stomp_url(&self) -> String {
+        format!("stomp://{}", self.address(STOMP_PORT))
+    }
+
+    pub fn console_url(&self) -> String {
+        format!("http://{}/console", self.address(CONSOLE_PORT))
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+
+    fn address(&self, port: u16) -> String {
+        format!(
+            "{}:{}",
+            self.container.get_host(),
+            self.container
+                .get_host_port(format!("{port}/tcp"))
+                .unwrap_or(port)
+        )
+    }
+}
+
+impl From<RunningContainer> for ArtemisContainer {
+    fn from(container: RunningContainer) -> Self {
+        ArtemisContainer { container }
+    }
+}
+
+impl Deref for ArtemisContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpRequest,
+    modules::{command_line, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use hyper::Method;
+use serde_json::json;
+use std::{ops::Deref, sync::Arc};
+
+const HTTP_PORT: u16 = 8500;
+const GRPC_PORT: u16 = 8502;
+const DNS_PORT: u16 = 8600;
+
+pub async fn consul() -> GenericContainerBuilder {
+    ModuleBuilder::new(Consul).into_builder()
+}
+
+pub struct Consul;
+
+impl ContainerModule for Consul {
+    type Container = ConsulContainer;
+
+    fn image(&self) -> String {
+        "hashicorp/consul:1.18".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT, GRPC_PORT, DNS_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        command_line(&["agent", "-dev", "-client", "0.0.0.0"])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(LeaderElected))
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        builder.add_exposed_port(None, format!("{DNS_PORT}/udp"))
+    }
+}
+
+struct LeaderElected;
+
+#[async_trait]
+impl WaitStrategy for LeaderElected {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container.get_host_port(format!("{HTTP_PORT}/tcp")).await? else {
+            return Ok(false);
+        };
+        let url = format!("http://{}:{port}/v1/status/leader", container.host());
+        // the leader address is an empty json string until the election is over
+        Ok(match HttpRequest::new(Method::GET, url).send().await {
+            Ok((status, body)) => status.is_success() && !matches!(body.trim(), "" | "\"\""),
+            Err(_) => false,
+        })
+    }
+
+    fn describe(&self) -> String {
+        "consul leader elected".to_string()
+    }
+}
+
+pub struct ConsulContainer {
+    container: RunningContainer,
+}
+
+impl ConsulContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn get_grpc_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{GRPC_PORT}/tcp"))
+    }
+
+    pub fn get_dns_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{DNS_PORT}/udp"))
+    }
+
+    pub fn http_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    pub async fn put_kv(&self, key: &str, value: &str) -> Result<(), TestcontainersError> {
+        HttpRequest::new(Method::PUT, format!("{}/v1/kv/{key}", self.http_url()))
+            .with_text(value)
+            .send_expecting_success()
+            .await?;
+        Ok(())
+    }
+
+    pub async fn register_service(
+        &self,
+        name: &str,
+        address: &str,
+        port: u16,
+    ) -> Result<(), TestcontainersError> {
+        HttpRequest::new(
+            Method::PUT,
+            format!("{}/v1/agent/service/register", self.http_url()),
+        )
+        .with_json(json!({ "Name": name, "Address": address, "Port": port }))
+        .send_expecting_success()
+        .await?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for ConsulContainer {
+    fn from(container: RunningContainer) -> Self {
+        ConsulContainer { container }
+    }
+}
+
+impl Deref for ConsulContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,28 @@
+use crate::error::TestcontainersError;
+use diesel::{backend::Backend, Connection};
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
+
+pub(crate) fn establish<C: Connection>(url: &str) -> Result<C, TestcontainersError> {
+    C::establish(url).map_err(|error| {
+        TestcontainersError::Provisioning(format!(
+            "unable to establish a diesel connection: {error}"
+        ))
+    })
+}
+
+pub(crate) fn run_pending_migrations<C, DB>(
+    url: &str,
+    migrations: EmbeddedMigrations,
+) -> Result<(), TestcontainersError>
+where
+    C: Connection<Backend = DB> + MigrationHarness<DB>,
+    DB: Backend,
+{
+    let mut connection = establish::<C>(url)?;
+    connection
+        .run_pending_migrations(migrations)
+        .map_err(|error| {
+            TestcontainersError::Provisioning(format!("unable to run the migrations: {error}"))
+        })?;
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpRequest,
+    modules::{env_vars, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use hyper::Method;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+    time::SystemTime,
+};
+
+const HTTP_PORT: u16 = 9200;
+const ELASTIC_USERNAME: &str = "elastic";
+// the default 1g heap does not leave much room for anything else on small CI runners
+const JAVA_OPTS: &str = "-Xms512m -Xmx512m";
+
+pub async fn elasticsearch() -> GenericContainerBuilder {
+    ModuleBuilder::new(Elasticsearch::default()).into_builder()
+}
+
+pub async fn elasticsearch_secured() -> GenericContainerBuilder {
+    ModuleBuilder::new(Elasticsearch::secured()).into_builder()
+}
+
+pub async fn opensearch() -> GenericContainerBuilder {
+    ModuleBuilder::new(OpenSearch).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Elasticsearch {
+    password: Option<String>,
+}
+
+impl Elasticsearch {
+    // security is enabled with a generated password for the elastic user, TLS is left disabled
+    pub fn secured() -> Self {
+        Elasticsearch {
+            password: Some(generate_password()),
+        }
+    }
+}
+
+impl ContainerModule for Elasticsearch {
+    type Container = ElasticsearchContainer;
+
+    fn image(&self) -> String {
+        "docker.elastic.co/elasticsearch/elasticsearch:8.13.4".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        let mut env = env_vars(&[
+            ("discovery.type", "single-node"),
+            ("ES_JAVA_OPTS", JAVA_OPTS),
+        ]);
+        match &self.password {
+            Some(password) => env.extend(env_vars(&[
+                ("xpack.security.enabled", "true"),
+                ("xpack.security.http.ssl.enabled", "false"),
+                ("ELASTIC_PASSWORD", password),
+            ])),
+            None => env.extend(env_vars(&[("xpack.security.enabled", "false")])),
+        }
+        env
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ClusterHealthy {
+            credentials: self
+                .password
+                .as_ref()
+                .map(|password| (ELASTIC_USERNAME.to_string(), password.clone())),
+        }))
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        with_search_ulimits(builder)
+    }
+}
+
+pub struct OpenSearch;
+
+impl ContainerModule for OpenSearch {
+    type Container = ElasticsearchContainer;
+
+    fn image(&self) -> String {
+        "opensearchproject/opensearch:2.13.0".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[
+            ("discovery.type", "single-node"),
+            ("DISABLE_SECURITY_PLUGIN", "true"),
+            ("DISABLE_INSTALL_DEMO_CONFIG", "true"),
+            ("OPENSEARCH_JAVA_OPTS", JAVA_OPTS),
+        ])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ClusterHealthy { credentials: None }))
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        with_search_ulimits(builder)
+    }
+}
+
+fn with_search_ulimits(builder: GenericContainerBuilder) -> GenericContainerBuilder {
+    builder
+        .with_ulimit("nofile", 65535, 65535)
+        .with_ulimit("memlock", -1, -1)
+}
+
+fn generate_password() -> String {
+    let mut hasher = DefaultHasher::new();
+    std::process::id().hash(&mut hasher);
+    SystemTime::now().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+struct ClusterHealthy {
+    credentials: Option<(String, String)>,
+}
+
+#[async_trait]
+impl WaitStrategy for ClusterHealthy {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container.get_host_port(format!("{HTTP_PORT}/tcp")).await? else {
+            return Ok(false);
+        };
+        // a single node cluster never allocates replicas, so yellow is as good as it gets
+        let mut request = HttpRequest::new(
+            Method::GET,
+            format!(
+                "http://{}:{port}/_cluster/health?wait_for_status=yellow&timeout=1s",
+                container.host()
+            ),
+        );
+        if let Some((username, password)) = &self.credentials {
+            request = request.with_basic_auth(username, password);
+        }
+        Ok(matches!(request.send().await, Ok((status, _)) if status.is_success()))
+    }
+
+    fn describe(&self) -> String {
+        "cluster health is yellow".to_string()
+    }
+}
+
+pub struct ElasticsearchContainer {
+    container: RunningContainer,
+}
+
+impl ElasticsearchContainer {
+    pub fn username(&self) -> Option<&str> {
+        self.password().map(|_| ELASTIC_USERNAME)
+    }
+
+    pub fn password(&self) -> Option<&str> {
+        self.container.get_env("ELASTIC_PASSWORD")
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn http_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for ElasticsearchContainer {
+    fn from(container: RunningContainer) -> Self {
+        ElasticsearchContainer { container }
+    }
+}
+
+impl Deref for ElasticsearchContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
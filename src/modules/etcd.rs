@@ -0,0 +1,140 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpGetSucceeds,
+    modules::{command_line, env_vars, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use std::{ops::Deref, sync::Arc};
+
+const CLIENT_PORT: u16 = 2379;
+const PEER_PORT: u16 = 2380;
+const NODE_NAME: &str = "etcd0";
+const ADVERTISED_CLIENT_URLS_FILE: &str = "/tmp/testcontainers_advertised_client_urls";
+
+pub async fn etcd() -> GenericContainerBuilder {
+    ModuleBuilder::new(Etcd).into_builder()
+}
+
+pub struct Etcd;
+
+impl ContainerModule for Etcd {
+    type Container = EtcdContainer;
+
+    fn image(&self) -> String {
+        "bitnami/etcd:3.5".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[
+            ("ALLOW_NONE_AUTHENTICATION", "yes"),
+            ("ETCD_NAME", NODE_NAME),
+            (
+                "ETCD_LISTEN_CLIENT_URLS",
+                &format!("http://0.0.0.0:{CLIENT_PORT}"),
+            ),
+            (
+                "ETCD_LISTEN_PEER_URLS",
+                &format!("http://0.0.0.0:{PEER_PORT}"),
+            ),
+            (
+                "ETCD_INITIAL_ADVERTISE_PEER_URLS",
+                &format!("http://localhost:{PEER_PORT}"),
+            ),
+            (
+                "ETCD_INITIAL_CLUSTER",
+                &format!("{NODE_NAME}=http://localhost:{PEER_PORT}"),
+            ),
+            ("ETCD_INITIAL_CLUSTER_STATE", "new"),
+        ])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![CLIENT_PORT]
+    }
+
+    // like kafka, etcd advertises the mapped client port, so it only starts once the strategy
+    // wrote it down
+    fn command(&self) -> Option<Vec<String>> {
+        command_line(&[
+            "sh",
+            "-c",
+            &format!(
+                "while [ ! -f {ADVERTISED_CLIENT_URLS_FILE} ]; do sleep 0.1; done; \
+                 export ETCD_ADVERTISE_CLIENT_URLS=\"$(cat {ADVERTISED_CLIENT_URLS_FILE})\"; \
+                 exec /opt/bitnami/scripts/etcd/entrypoint.sh /opt/bitnami/scripts/etcd/run.sh"
+            ),
+        ])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(AdvertisedNodeHealthy))
+    }
+}
+
+struct AdvertisedNodeHealthy;
+
+#[async_trait]
+impl WaitStrategy for AdvertisedNodeHealthy {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container
+            .get_host_port(format!("{CLIENT_PORT}/tcp"))
+            .await?
+        else {
+            return Ok(false);
+        };
+        let client_urls = format!("http://{}:{port}", container.host());
+        container
+            .exec(&[
+                "sh",
+                "-c",
+                &format!(
+                    "[ -f {ADVERTISED_CLIENT_URLS_FILE} ] || echo \"{client_urls}\" > {ADVERTISED_CLIENT_URLS_FILE}"
+                ),
+            ])
+            .await?;
+        HttpGetSucceeds::new(CLIENT_PORT, "/health")
+            .is_ready(container)
+            .await
+    }
+
+    fn describe(&self) -> String {
+        "etcd node healthy with advertised client urls".to_string()
+    }
+}
+
+pub struct EtcdContainer {
+    container: RunningContainer,
+}
+
+impl EtcdContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{CLIENT_PORT}/tcp"))
+    }
+
+    pub fn client_endpoint(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(CLIENT_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for EtcdContainer {
+    fn from(container: RunningContainer) -> Self {
+        EtcdContainer { container }
+    }
+}
+
+impl Deref for EtcdContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
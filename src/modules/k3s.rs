@@ -0,0 +1,128 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    modules::{command_line, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use std::{ops::Deref, sync::Arc};
+
+const API_SERVER_PORT: u16 = 6443;
+const KUBECONFIG: &str = "/etc/rancher/k3s/k3s.yaml";
+const TLS_SAN_FILE: &str = "/tmp/testcontainers_tls_san";
+
+pub async fn k3s() -> GenericContainerBuilder {
+    ModuleBuilder::new(K3s).into_builder()
+}
+
+pub struct K3s;
+
+impl ContainerModule for K3s {
+    type Container = K3sContainer;
+
+    fn image(&self) -> String {
+        "rancher/k3s:v1.29.4-k3s1".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![API_SERVER_PORT]
+    }
+
+    // the api server certificate must be valid for the docker host, which is only known once
+    // the container runs, so the server starts after the wait strategy wrote it down
+    fn command(&self) -> Option<Vec<String>> {
+        command_line(&[
+            "sh",
+            "-c",
+            &format!(
+                "while [ ! -f {TLS_SAN_FILE} ]; do sleep 0.1; done; \
+                 exec k3s server --disable=traefik --tls-san=\"$(cat {TLS_SAN_FILE})\""
+            ),
+        ])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ApiServerReady))
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        builder.with_privileged(true).with_start_timeout("120s")
+    }
+}
+
+struct ApiServerReady;
+
+#[async_trait]
+impl WaitStrategy for ApiServerReady {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        container
+            .exec(&[
+                "sh",
+                "-c",
+                &format!(
+                    "[ -f {TLS_SAN_FILE} ] || echo \"{}\" > {TLS_SAN_FILE}",
+                    container.host()
+                ),
+            ])
+            .await?;
+        let result = container
+            .exec(&["kubectl", "get", "--raw", "/readyz"])
+            .await?;
+        Ok(result.exit_code == 0 && result.stdout.trim() == "ok")
+    }
+
+    fn describe(&self) -> String {
+        "k3s api server ready".to_string()
+    }
+}
+
+pub struct K3sContainer {
+    container: RunningContainer,
+}
+
+impl K3sContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container
+            .get_host_port(format!("{API_SERVER_PORT}/tcp"))
+    }
+
+    pub fn server_url(&self) -> String {
+        format!(
+            "https://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(API_SERVER_PORT)
+        )
+    }
+
+    // the admin kubeconfig written by k3s, pointing at the mapped api server port
+    pub async fn kubeconfig(&self) -> Result<String, TestcontainersError> {
+        let result = self.container.exec(&["cat", KUBECONFIG]).await?;
+        if result.exit_code != 0 {
+            return Err(TestcontainersError::Provisioning(format!(
+                "unable to read {KUBECONFIG}: {}",
+                result.stderr.trim()
+            )));
+        }
+        Ok(result.stdout.replace(
+            &format!("https://127.0.0.1:{API_SERVER_PORT}"),
+            &self.server_url(),
+        ))
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for K3sContainer {
+    fn from(container: RunningContainer) -> Self {
+        K3sContainer { container }
+    }
+}
+
+impl Deref for K3sContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    modules::{command_line, env_vars, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use std::{ops::Deref, sync::Arc};
+
+const KAFKA_PORT: u16 = 9092;
+const ADVERTISED_LISTENERS_FILE: &str = "/tmp/testcontainers_advertised_listeners";
+
+pub async fn kafka() -> GenericContainerBuilder {
+    ModuleBuilder::new(Kafka).into_builder()
+}
+
+pub struct Kafka;
+
+impl ContainerModule for Kafka {
+    type Container = KafkaContainer;
+
+    fn image(&self) -> String {
+        "apache/kafka:3.7.0".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[
+            ("KAFKA_NODE_ID", "1"),
+            ("KAFKA_PROCESS_ROLES", "broker,controller"),
+            (
+                "KAFKA_LISTENERS",
+                "PLAINTEXT://:9092,BROKER://:9093,CONTROLLER://:9094",
+            ),
+            (
+                "KAFKA_LISTENER_SECURITY_PROTOCOL_MAP",
+                "PLAINTEXT:PLAINTEXT,BROKER:PLAINTEXT,CONTROLLER:PLAINTEXT",
+            ),
+            ("KAFKA_INTER_BROKER_LISTENER_NAME", "BROKER"),
+            ("KAFKA_CONTROLLER_LISTENER_NAMES", "CONTROLLER"),
+            ("KAFKA_CONTROLLER_QUORUM_VOTERS", "1@localhost:9094"),
+            ("KAFKA_OFFSETS_TOPIC_REPLICATION_FACTOR", "1"),
+            ("KAFKA_TRANSACTION_STATE_LOG_REPLICATION_FACTOR", "1"),
+            ("KAFKA_TRANSACTION_STATE_LOG_MIN_ISR", "1"),
+            ("KAFKA_GROUP_INITIAL_REBALANCE_DELAY_MS", "0"),
+        ])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![KAFKA_PORT]
+    }
+
+    // the broker only starts once the advertised listeners file exists, which can't be written
+    // before the host port is mapped
+    fn command(&self) -> Option<Vec<String>> {
+        command_line(&[
+            "sh",
+            "-c",
+            &format!(
+                "while [ ! -f {ADVERTISED_LISTENERS_FILE} ]; do sleep 0.1; done; \
+                 export KAFKA_ADVERTISED_LISTENERS=\"$(cat {ADVERTISED_LISTENERS_FILE})\"; \
+                 exec /etc/kafka/docker/run"
+            ),
+        ])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(AdvertisedBrokerStarted))
+    }
+}
+
+struct AdvertisedBrokerStarted;
+
+#[async_trait]
+impl WaitStrategy for AdvertisedBrokerStarted {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container.get_host_port(format!("{KAFKA_PORT}/tcp")).await? else {
+            return Ok(false);
+        };
+        let listeners = format!(
+            "PLAINTEXT://{}:{port},BROKER://$(hostname):9093",
+            container.host()
+        );
+        container
+            .exec(&[
+                "sh",
+                "-c",
+                &format!(
+                    "[ -f {ADVERTISED_LISTENERS_FILE} ] || echo \"{listeners}\" > {ADVERTISED_LISTENERS_FILE}"
+                ),
+            ])
+            .await?;
+        Ok(container.logs().await?.contains("Kafka Server started"))
+    }
+
+    fn describe(&self) -> String {
+        "kafka broker started with advertised listeners".to_string()
+    }
+}
+
+pub struct KafkaContainer {
+    container: RunningContainer,
+}
+
+impl KafkaContainer {
+    pub fn bootstrap_servers(&self) -> String {
+        format!(
+            "{}:{}",
+            self.container.get_host(),
+            self.container
+                .get_host_port(format!("{KAFKA_PORT}/tcp"))
+                .unwrap_or(KAFKA_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for KafkaContainer {
+    fn from(container: RunningContainer) -> Self {
+        KafkaContainer { container }
+    }
+}
+
+impl Deref for KafkaContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
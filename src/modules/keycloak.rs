@@ -0,0 +1,125 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    http::HttpGetSucceeds,
+    modules::{command_line, env_vars, ContainerModule, ModuleBuilder},
+};
+use std::{ops::Deref, path::PathBuf, sync::Arc};
+
+const HTTP_PORT: u16 = 8080;
+const IMPORT_DIR: &str = "/opt/keycloak/data/import";
+
+pub async fn keycloak() -> GenericContainerBuilder {
+    ModuleBuilder::new(Keycloak::default()).into_builder()
+}
+
+pub async fn keycloak_with_realm_import<P: Into<PathBuf>>(
+    realm_file: P,
+) -> GenericContainerBuilder {
+    ModuleBuilder::new(Keycloak::default().with_realm_import(realm_file)).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Keycloak {
+    realm_files: Vec<PathBuf>,
+}
+
+impl Keycloak {
+    // the realm export is imported on startup, a realm that already exists is left untouched
+    pub fn with_realm_import<P: Into<PathBuf>>(mut self, realm_file: P) -> Self {
+        self.realm_files.push(realm_file.into());
+        self
+    }
+}
+
+impl ContainerModule for Keycloak {
+    type Container = KeycloakContainer;
+
+    fn image(&self) -> String {
+        "quay.io/keycloak/keycloak:24.0".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[
+            ("KEYCLOAK_ADMIN", "admin"),
+            ("KEYCLOAK_ADMIN_PASSWORD", "admin"),
+            ("KC_HEALTH_ENABLED", "true"),
+        ])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        match self.realm_files.is_empty() {
+            true => command_line(&["start-dev"]),
+            false => command_line(&["start-dev", "--import-realm"]),
+        }
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(HTTP_PORT, "/health/ready")))
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        self.realm_files
+            .iter()
+            .fold(builder, |builder, realm_file| {
+                let file_name = realm_file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "realm.json".to_string());
+                builder.copy_file_to_container(realm_file, format!("{IMPORT_DIR}/{file_name}"))
+            })
+    }
+}
+
+pub struct KeycloakContainer {
+    container: RunningContainer,
+}
+
+impl KeycloakContainer {
+    pub fn admin_username(&self) -> &str {
+        self.container.get_env("KEYCLOAK_ADMIN").unwrap_or_default()
+    }
+
+    pub fn admin_password(&self) -> &str {
+        self.container
+            .get_env("KEYCLOAK_ADMIN_PASSWORD")
+            .unwrap_or_default()
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn auth_server_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    pub fn issuer_url(&self, realm: &str) -> String {
+        format!("{}/realms/{realm}", self.auth_server_url())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for KeycloakContainer {
+    fn from(container: RunningContainer) -> Self {
+        KeycloakContainer { container }
+    }
+}
+
+impl Deref for KeycloakContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpRequest,
+    modules::{env_vars, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use hyper::Method;
+use std::{fmt::Display, ops::Deref, sync::Arc};
+
+const EDGE_PORT: u16 = 4566;
+const DEFAULT_REGION: &str = "us-east-1";
+// localstack accepts any credentials, these are the ones its documentation uses
+const ACCESS_KEY_ID: &str = "test";
+const SECRET_ACCESS_KEY: &str = "test";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalStackService {
+    CloudWatch,
+    DynamoDb,
+    Iam,
+    Kinesis,
+    Lambda,
+    S3,
+    SecretsManager,
+    Sns,
+    Sqs,
+    Ssm,
+    Sts,
+}
+
+impl LocalStackService {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LocalStackService::CloudWatch => "cloudwatch",
+            LocalStackService::DynamoDb => "dynamodb",
+            LocalStackService::Iam => "iam",
+            LocalStackService::Kinesis => "kinesis",
+            LocalStackService::Lambda => "lambda",
+            LocalStackService::S3 => "s3",
+            LocalStackService::SecretsManager => "secretsmanager",
+            LocalStackService::Sns => "sns",
+            LocalStackService::Sqs => "sqs",
+            LocalStackService::Ssm => "ssm",
+            LocalStackService::Sts => "sts",
+        }
+    }
+}
+
+impl Display for LocalStackService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub async fn localstack() -> GenericContainerBuilder {
+    ModuleBuilder::new(LocalStack::default()).into_builder()
+}
+
+pub async fn localstack_with_services(services: &[LocalStackService]) -> GenericContainerBuilder {
+    ModuleBuilder::new(LocalStack::default().with_services(services)).into_builder()
+}
+
+// every service is available and lazily started on its first use, unless some are selected
+#[derive(Debug, Clone, Default)]
+pub struct LocalStack {
+    services: Vec<LocalStackService>,
+}
+
+impl LocalStack {
+    pub fn with_services(mut self, services: &[LocalStackService]) -> Self {
+        self.services.extend_from_slice(services);
+        self
+    }
+
+    fn service_names(&self) -> Vec<&'static str> {
+        self.services.iter().map(|service| service.name()).collect()
+    }
+}
+
+impl ContainerModule for LocalStack {
+    type Container = LocalStackContainer;
+
+    fn image(&self) -> String {
+        "localstack/localstack:3".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        let services = self.service_names().join(",");
+        let mut env = env_vars(&[("AWS_DEFAULT_REGION", DEFAULT_REGION)]);
+        if !services.is_empty() {
+            env.extend(env_vars(&[("SERVICES", &services)]));
+        }
+        env
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![EDGE_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ServicesAvailable {
+            services: self.service_names(),
+        }))
+    }
+}
+
+struct ServicesAvailable {
+    services: Vec<&'static str>,
+}
+
+#[async_trait]
+impl WaitStrategy for ServicesAvailable {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container.get_host_port(format!("{EDGE_PORT}/tcp")).await? else {
+            return Ok(false);
+        };
+        let url = format!("http://{}:{port}/_localstack/health", container.host());
+        let Ok((status, body)) = HttpRequest::new(Method::GET, url).send().await else {
+            return Ok(false);
+        };
+        let Ok(health) = serde_json::from_str::<serde_json::Value>(&body) else {
+            return Ok(false);
+        };
+        Ok(status.is_success()
+            && self.services.iter().all(|service| {
+                matches!(
+                    health["services"][service].as_str(),
+                    Some("available" | "running")
+                )
+            }))
+    }
+
+    fn describe(&self) -> String {
+        match self.services.is_empty() {
+            true => "localstack health endpoint available".to_string(),
+            false => format!("localstack services {} available", self.services.join(",")),
+        }
+    }
+}
+
+pub struct LocalStackContainer {
+    container: RunningContainer,
+}
+
+impl LocalStackContainer {
+    pub fn region(&self) -> &str {
+        self.container
+            .get_env("AWS_DEFAULT_REGION")
+            .unwrap_or(DEFAULT_REGION)
+    }
+
+    pub fn access_key_id(&self) -> &str {
+        ACCESS_KEY_ID
+    }
+
+    pub fn secret_access_key(&self) -> &str {
+        SECRET_ACCESS_KEY
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{EDGE_PORT}/tcp"))
+    }
+
+    // all services share the edge port, none is returned for a service that was not selected
+    pub fn endpoint_url(&self, service: LocalStackService) -> Option<String> {
+        let enabled = match self.container.get_env("SERVICES") {
+            Some(services) => services.split(',').any(|name| name == service.name()),
+            None => true,
+        };
+        enabled.then(|| self.edge_url())
+    }
+
+    // region, credentials and endpoint of the container, ready for any aws sdk client
+    #[cfg(feature = "aws-config")]
+    pub fn sdk_config(&self) -> aws_config::SdkConfig {
+        use aws_config::{BehaviorVersion, Region, SdkConfig};
+        use aws_credential_types::{provider::SharedCredentialsProvider, Credentials};
+
+        let credentials = Credentials::new(
+            ACCESS_KEY_ID,
+            SECRET_ACCESS_KEY,
+            None,
+            None,
+            "testcontainers-localstack",
+        );
+        SdkConfig::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(self.region().to_string()))
+            .credentials_provider(SharedCredentialsProvider::new(credentials))
+            .endpoint_url(self.edge_url())
+            .build()
+    }
+
+    fn edge_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(EDGE_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for LocalStackContainer {
+    fn from(container: RunningContainer) -> Self {
+        LocalStackContainer { container }
+    }
+}
+
+impl Deref for LocalStackContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::{HttpGetSucceeds, HttpRequest},
+    modules::{ContainerModule, ModuleBuilder},
+};
+use hyper::Method;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const SMTP_PORT: u16 = 1025;
+const HTTP_PORT: u16 = 8025;
+
+pub async fn mailpit() -> GenericContainerBuilder {
+    ModuleBuilder::new(Mailpit).into_builder()
+}
+
+pub struct Mailpit;
+
+impl ContainerModule for Mailpit {
+    type Container = MailpitContainer;
+
+    fn image(&self) -> String {
+        "axllent/mailpit:v1.18".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![SMTP_PORT, HTTP_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(HTTP_PORT, "/api/v1/info")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedEmail {
+    pub id: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub subject: String,
+    pub text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Address {
+    address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MessagesResponse {
+    messages: Vec<MessageSummary>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MessageSummary {
+    #[serde(rename = "ID")]
+    id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Message {
+    #[serde(rename = "ID")]
+    id: String,
+    from: Option<Address>,
+    #[serde(default)]
+    to: Vec<Address>,
+    subject: String,
+    text: String,
+}
+
+pub struct MailpitContainer {
+    container: RunningContainer,
+}
+
+impl MailpitContainer {
+    pub fn get_smtp_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{SMTP_PORT}/tcp"))
+    }
+
+    pub fn get_http_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn smtp_host_and_port(&self) -> (String, u16) {
+        (
+            self.container.get_host(),
+            self.get_smtp_host_port().unwrap_or(SMTP_PORT),
+        )
+    }
+
+    pub fn http_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_http_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    // most recent first
+    pub async fn received_emails(&self) -> Result<Vec<ReceivedEmail>, TestcontainersError> {
+        let summaries: MessagesResponse = self.get("/api/v1/messages").await?;
+        let mut emails = Vec::with_capacity(summaries.messages.len());
+        for summary in summaries.messages {
+            let message: Message = self.get(&format!("/api/v1/message/{}", summary.id)).await?;
+            emails.push(ReceivedEmail {
+                id: message.id,
+                from: message.from.map(|from| from.address).unwrap_or_default(),
+                to: message.to.into_iter().map(|to| to.address).collect(),
+                subject: message.subject,
+                text: message.text,
+            });
+        }
+        Ok(emails)
+    }
+
+    // delivery is asynchronous, so the emails sent by the system under test may not be there yet
+    pub async fn wait_for_emails(
+        &self,
+        count: usize,
+        timeout: Duration,
+    ) -> Result<Vec<ReceivedEmail>, TestcontainersError> {
+        let started = Instant::now();
+        loop {
+            let emails = self.received_emails().await?;
+            if emails.len() >= count {
+                return Ok(emails);
+            }
+            if started.elapsed() > timeout {
+                return Err(TestcontainersError::Provisioning(format!(
+                    "expected {count} emails, {} received after {timeout:?}",
+                    emails.len()
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    pub async fn delete_all_emails(&self) -> Result<(), TestcontainersError> {
+        HttpRequest::new(
+            Method::DELETE,
+            format!("{}/api/v1/messages", self.http_url()),
+        )
+        .send_expecting_success()
+        .await?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, TestcontainersError> {
+        let body = HttpRequest::new(Method::GET, format!("{}{path}", self.http_url()))
+            .send_expecting_success()
+            .await?;
+        serde_json::from_str(&body).map_err(|error| TestcontainersError::Http(error.to_string()))
+    }
+}
+
+impl From<RunningContainer> for MailpitContainer {
+    fn from(container: RunningContainer) -> Self {
+        MailpitContainer { container }
+    }
+}
+
+impl Deref for MailpitContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
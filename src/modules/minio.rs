@@ -0,0 +1,157 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    docker_client::ExecOptions,
+    error::TestcontainersError,
+    http::HttpGetSucceeds,
+    modules::{command_line, env_vars, ContainerModule, ModuleBuilder},
+};
+use async_trait::async_trait;
+use std::{ops::Deref, sync::Arc};
+
+const API_PORT: u16 = 9000;
+const CONSOLE_PORT: u16 = 9001;
+const DEFAULT_CREDENTIAL: &str = "minioadmin";
+
+pub async fn minio() -> GenericContainerBuilder {
+    ModuleBuilder::new(Minio::default()).into_builder()
+}
+
+// the buckets are created once the server is ready, when started through a ModuleBuilder
+#[derive(Default)]
+pub struct Minio {
+    buckets: Vec<String>,
+}
+
+impl Minio {
+    pub fn with_bucket<S: Into<String>>(mut self, bucket: S) -> Self {
+        self.buckets.push(bucket.into());
+        self
+    }
+}
+
+#[async_trait]
+impl ContainerModule for Minio {
+    type Container = MinioContainer;
+
+    fn image(&self) -> String {
+        "minio/minio:latest".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[
+            ("MINIO_ROOT_USER", DEFAULT_CREDENTIAL),
+            ("MINIO_ROOT_PASSWORD", DEFAULT_CREDENTIAL),
+        ])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![API_PORT, CONSOLE_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        command_line(&[
+            "server",
+            "/data",
+            "--console-address",
+            &format!(":{CONSOLE_PORT}"),
+        ])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(
+            API_PORT,
+            "/minio/health/ready",
+        )))
+    }
+
+    async fn after_start(&self, container: &MinioContainer) -> Result<(), TestcontainersError> {
+        for bucket in &self.buckets {
+            container.create_bucket(bucket).await?;
+        }
+        Ok(())
+    }
+}
+
+pub struct MinioContainer {
+    container: RunningContainer,
+}
+
+impl MinioContainer {
+    pub fn access_key(&self) -> &str {
+        self.container
+            .get_env("MINIO_ROOT_USER")
+            .unwrap_or(DEFAULT_CREDENTIAL)
+    }
+
+    pub fn secret_key(&self) -> &str {
+        self.container
+            .get_env("MINIO_ROOT_PASSWORD")
+            .unwrap_or(DEFAULT_CREDENTIAL)
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{API_PORT}/tcp"))
+    }
+
+    pub fn get_console_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{CONSOLE_PORT}/tcp"))
+    }
+
+    pub fn api_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(API_PORT)
+        )
+    }
+
+    pub fn console_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_console_host_port().unwrap_or(CONSOLE_PORT)
+        )
+    }
+
+    // the image ships the mc client, which spares signing S3 requests here
+    pub async fn create_bucket(&self, bucket: &str) -> Result<(), TestcontainersError> {
+        let options = ExecOptions::default().add_env(
+            "MC_HOST_local".to_string(),
+            format!(
+                "http://{}:{}@localhost:{API_PORT}",
+                self.access_key(),
+                self.secret_key()
+            ),
+        );
+        let target = format!("local/{bucket}");
+        let result = self
+            .container
+            .exec_with(&["mc", "mb", "--ignore-existing", &target], &options)
+            .await?;
+        if result.exit_code != 0 {
+            return Err(TestcontainersError::Provisioning(format!(
+                "unable to create bucket {bucket}: {}",
+                result.stderr.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for MinioContainer {
+    fn from(container: RunningContainer) -> Self {
+        MinioContainer { container }
+    }
+}
+
+impl Deref for MinioContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,122 @@
+pub mod artemis;
+pub mod consul;
+#[cfg(any(feature = "diesel-mysql", feature = "diesel-postgres"))]
+mod diesel_connection;
+pub mod elasticsearch;
+pub mod etcd;
+pub mod k3s;
+pub mod kafka;
+pub mod keycloak;
+pub mod localstack;
+pub mod mailpit;
+pub mod minio;
+pub mod mongodb;
+pub mod mysql;
+pub mod nats;
+pub mod nginx;
+pub mod ollama;
+pub mod openldap;
+pub mod postgresql;
+pub mod pulsar;
+pub mod rabbitmq;
+pub mod redis;
+pub mod scylla;
+pub mod sftp;
+pub mod toxiproxy;
+pub mod trino;
+pub mod wiremock;
+
+use crate::{
+    container::{GenericContainer, GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+};
+use async_trait::async_trait;
+
+// a module describes a container and the typed wrapper tests use once it runs, other crates
+// can implement it to publish their own modules. every module of this crate is one, its preset
+// functions being shorthands for ModuleBuilder::new(module).into_builder()
+#[async_trait]
+pub trait ContainerModule: Send + Sync {
+    type Container: From<RunningContainer> + Send + Sync;
+
+    fn image(&self) -> String;
+
+    fn env(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::None
+    }
+
+    // for anything else the builder supports, e.g. a longer start timeout
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        builder
+    }
+
+    // runs once the container is ready, e.g. to provision it
+    async fn after_start(&self, _container: &Self::Container) -> Result<(), TestcontainersError> {
+        Ok(())
+    }
+}
+
+pub struct ModuleBuilder<M: ContainerModule> {
+    module: M,
+    builder: GenericContainerBuilder,
+}
+
+impl<M: ContainerModule> ModuleBuilder<M> {
+    pub fn new(module: M) -> Self {
+        let mut builder = GenericContainer::from_image(module.image());
+        for (key, value) in module.env() {
+            builder = builder.add_env(key, value);
+        }
+        for port in module.exposed_ports() {
+            builder = builder.add_exposed_tcp_port(port);
+        }
+        if let Some(command) = module.command() {
+            let command: Vec<_> = command.iter().map(String::as_str).collect();
+            builder = builder.with_command(&command);
+        }
+        let builder = builder.with_wait_strategy_on_startup(module.wait_strategy());
+        let builder = module.customize(builder);
+        ModuleBuilder { module, builder }
+    }
+
+    pub fn with<F>(mut self, configure: F) -> Self
+    where
+        F: FnOnce(GenericContainerBuilder) -> GenericContainerBuilder,
+    {
+        self.builder = configure(self.builder);
+        self
+    }
+
+    // the after start hook is not run for containers started from the plain builder
+    pub fn into_builder(self) -> GenericContainerBuilder {
+        self.builder
+    }
+
+    pub async fn start(self) -> Result<M::Container, TestcontainersError> {
+        let container = M::Container::from(self.builder.create().await?.start().await?);
+        self.module.after_start(&container).await?;
+        Ok(container)
+    }
+}
+
+pub(crate) fn env_vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+pub(crate) fn command_line(parts: &[&str]) -> Option<Vec<String>> {
+    Some(parts.iter().map(|part| part.to_string()).collect())
+}
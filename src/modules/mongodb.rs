@@ -0,0 +1,133 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    docker_client::LogOptions,
+    error::TestcontainersError,
+    modules::{command_line, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use std::{ops::Deref, sync::Arc};
+
+const MONGODB_PORT: u16 = 27017;
+const REPLICA_SET: &str = "rs0";
+
+pub async fn mongodb() -> GenericContainerBuilder {
+    ModuleBuilder::new(MongoDb::default()).into_builder()
+}
+
+pub async fn mongodb_replica_set() -> GenericContainerBuilder {
+    ModuleBuilder::new(MongoDb::replica_set()).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MongoDb {
+    replica_set: bool,
+}
+
+impl MongoDb {
+    // transactions and change streams require a replica set, a single member one is enough
+    pub fn replica_set() -> Self {
+        MongoDb { replica_set: true }
+    }
+}
+
+impl ContainerModule for MongoDb {
+    type Container = MongoDbContainer;
+
+    fn image(&self) -> String {
+        "mongo:7".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![MONGODB_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        match self.replica_set {
+            true => command_line(&["--replSet", REPLICA_SET, "--bind_ip_all"]),
+            false => None,
+        }
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        match self.replica_set {
+            true => ReadyStrategy::Custom(Arc::new(ReplicaSetInitiated)),
+            false => ReadyStrategy::LogMessageRegExp(
+                "Waiting for connections".parse().unwrap(),
+                LogOptions::default(),
+            ),
+        }
+    }
+}
+
+struct ReplicaSetInitiated;
+
+#[async_trait]
+impl WaitStrategy for ReplicaSetInitiated {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let initiate = format!(
+            "try {{ rs.status().ok }} catch (error) {{ rs.initiate({{ _id: '{REPLICA_SET}', members: [{{ _id: 0, host: 'localhost:{MONGODB_PORT}' }}] }}).ok }}"
+        );
+        let result = container
+            .exec(&["mongosh", "--quiet", "--eval", &initiate])
+            .await?;
+        if result.exit_code != 0 {
+            return Ok(false);
+        }
+        let result = container
+            .exec(&[
+                "mongosh",
+                "--quiet",
+                "--eval",
+                "db.hello().isWritablePrimary",
+            ])
+            .await?;
+        Ok(result.exit_code == 0 && result.stdout.trim() == "true")
+    }
+
+    fn describe(&self) -> String {
+        format!("replica set {REPLICA_SET} initiated")
+    }
+}
+
+pub struct MongoDbContainer {
+    container: RunningContainer,
+}
+
+impl MongoDbContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{MONGODB_PORT}/tcp"))
+    }
+
+    // replica set members are advertised as localhost, so clients must not try to discover them
+    pub fn connection_string(&self) -> String {
+        let credentials = self
+            .container
+            .get_env("MONGO_INITDB_ROOT_USERNAME")
+            .zip(self.container.get_env("MONGO_INITDB_ROOT_PASSWORD"))
+            .map(|(username, password)| format!("{username}:{password}@"))
+            .unwrap_or_default();
+        format!(
+            "mongodb://{credentials}{}:{}/?directConnection=true",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(MONGODB_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for MongoDbContainer {
+    fn from(container: RunningContainer) -> Self {
+        MongoDbContainer { container }
+    }
+}
+
+impl Deref for MongoDbContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    docker_client::LogOptions,
+    modules::{env_vars, ContainerModule, ModuleBuilder},
+};
+use std::ops::Deref;
+
+const MYSQL_PORT: u16 = 3306;
+
+pub async fn mysql() -> GenericContainerBuilder {
+    ModuleBuilder::new(MySql).into_builder()
+}
+
+pub struct MySql;
+
+impl ContainerModule for MySql {
+    type Container = MySqlContainer;
+
+    fn image(&self) -> String {
+        "mysql:8".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[
+            ("MYSQL_DATABASE", "test"),
+            ("MYSQL_USER", "test"),
+            ("MYSQL_PASSWORD", "test"),
+            ("MYSQL_ROOT_PASSWORD", "test"),
+        ])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![MYSQL_PORT]
+    }
+
+    // the entrypoint first starts a temporary server without networking to run the init scripts
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::LogMessageRegExp(
+            r"ready for connections.*port: 3306\s".parse().unwrap(),
+            LogOptions::default(),
+        )
+    }
+}
+
+pub struct MySqlContainer {
+    container: RunningContainer,
+}
+
+impl MySqlContainer {
+    pub fn username(&self) -> &str {
+        self.container.get_env("MYSQL_USER").unwrap_or("root")
+    }
+
+    pub fn password(&self) -> &str {
+        match self.container.get_env("MYSQL_USER") {
+            Some(_) => self.container.get_env("MYSQL_PASSWORD"),
+            None => self.container.get_env("MYSQL_ROOT_PASSWORD"),
+        }
+        .unwrap_or_default()
+    }
+
+    pub fn database(&self) -> &str {
+        self.container.get_env("MYSQL_DATABASE").unwrap_or("mysql")
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{MYSQL_PORT}/tcp"))
+    }
+
+    pub fn connection_url(&self) -> String {
+        format!(
+            "mysql://{}:{}@{}:{}/{}",
+            self.username(),
+            self.password(),
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(MYSQL_PORT),
+            self.database()
+        )
+    }
+
+    // blocking, for the tests that don't use an async database stack
+    #[cfg(feature = "diesel-mysql")]
+    pub fn establish_diesel_connection(
+        &self,
+    ) -> Result<diesel::MysqlConnection, crate::error::TestcontainersError> {
+        super::diesel_connection::establish(&self.connection_url())
+    }
+
+    // migrations embedded with diesel_migrations::embed_migrations!, the ones already applied
+    // are skipped
+    #[cfg(feature = "diesel-mysql")]
+    pub fn run_diesel_migrations(
+        &self,
+        migrations: diesel_migrations::EmbeddedMigrations,
+    ) -> Result<(), crate::error::TestcontainersError> {
+        super::diesel_connection::run_pending_migrations::<diesel::MysqlConnection, _>(
+            &self.connection_url(),
+            migrations,
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for MySqlContainer {
+    fn from(container: RunningContainer) -> Self {
+        MySqlContainer { container }
+    }
+}
+
+impl Deref for MySqlContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
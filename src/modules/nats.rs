@@ -0,0 +1,103 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    http::HttpGetSucceeds,
+    modules::{command_line, ContainerModule, ModuleBuilder},
+};
+use std::{ops::Deref, sync::Arc};
+
+const CLIENT_PORT: u16 = 4222;
+const MONITORING_PORT: u16 = 8222;
+
+pub async fn nats() -> GenericContainerBuilder {
+    ModuleBuilder::new(Nats::default()).into_builder()
+}
+
+pub async fn nats_with_jetstream() -> GenericContainerBuilder {
+    ModuleBuilder::new(Nats::default().with_jetstream()).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Nats {
+    jetstream: bool,
+}
+
+impl Nats {
+    pub fn with_jetstream(mut self) -> Self {
+        self.jetstream = true;
+        self
+    }
+}
+
+impl ContainerModule for Nats {
+    type Container = NatsContainer;
+
+    fn image(&self) -> String {
+        "nats:2.10".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![CLIENT_PORT, MONITORING_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        let monitoring_port = MONITORING_PORT.to_string();
+        match self.jetstream {
+            true => command_line(&["--http_port", &monitoring_port, "--jetstream"]),
+            false => command_line(&["--http_port", &monitoring_port]),
+        }
+    }
+
+    // healthz also reports JetStream as unavailable until it is ready
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(MONITORING_PORT, "/healthz")))
+    }
+}
+
+pub struct NatsContainer {
+    container: RunningContainer,
+}
+
+impl NatsContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{CLIENT_PORT}/tcp"))
+    }
+
+    pub fn get_monitoring_host_port(&self) -> Option<u16> {
+        self.container
+            .get_host_port(format!("{MONITORING_PORT}/tcp"))
+    }
+
+    pub fn connection_url(&self) -> String {
+        format!(
+            "nats://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(CLIENT_PORT)
+        )
+    }
+
+    pub fn monitoring_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_monitoring_host_port().unwrap_or(MONITORING_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for NatsContainer {
+    fn from(container: RunningContainer) -> Self {
+        NatsContainer { container }
+    }
+}
+
+impl Deref for NatsContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpRequest,
+    modules::{ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use hyper::Method;
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+const HTTP_PORT: u16 = 80;
+const CONTENT_DIR: &str = "/usr/share/nginx/html";
+
+pub async fn nginx() -> GenericContainerBuilder {
+    ModuleBuilder::new(Nginx::default()).into_builder()
+}
+
+pub async fn nginx_with_static_content<P: AsRef<Path>>(content_dir: P) -> GenericContainerBuilder {
+    ModuleBuilder::new(Nginx::default().with_static_content(content_dir.as_ref())).into_builder()
+}
+
+pub async fn nginx_with_config<P: Into<PathBuf>>(config_file: P) -> GenericContainerBuilder {
+    ModuleBuilder::new(Nginx::default().with_config(config_file)).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Nginx {
+    content_dir: Option<PathBuf>,
+    config_file: Option<PathBuf>,
+}
+
+impl Nginx {
+    // replaces the default welcome page
+    pub fn with_static_content<P: Into<PathBuf>>(mut self, content_dir: P) -> Self {
+        self.content_dir = Some(content_dir.into());
+        self
+    }
+
+    // replaces the default server block, e.g. to proxy to another container of the network
+    pub fn with_config<P: Into<PathBuf>>(mut self, config_file: P) -> Self {
+        self.config_file = Some(config_file.into());
+        self
+    }
+}
+
+impl ContainerModule for Nginx {
+    type Container = NginxContainer;
+
+    fn image(&self) -> String {
+        "nginx:alpine".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ServerResponds))
+    }
+
+    fn customize(&self, mut builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        if let Some(content_dir) = &self.content_dir {
+            builder = builder.copy_dir_to_container(content_dir, CONTENT_DIR);
+        }
+        if let Some(config_file) = &self.config_file {
+            builder = builder.copy_file_to_container(config_file, "/etc/nginx/conf.d/default.conf");
+        }
+        builder
+    }
+}
+
+// the served content is up to the test, a 404 on / still means nginx is up
+struct ServerResponds;
+
+#[async_trait]
+impl WaitStrategy for ServerResponds {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container.get_host_port(format!("{HTTP_PORT}/tcp")).await? else {
+            return Ok(false);
+        };
+        let url = format!("http://{}:{port}/", container.host());
+        let response = HttpRequest::new(Method::GET, url).send().await;
+        Ok(matches!(response, Ok((status, _)) if !status.is_server_error()))
+    }
+
+    fn describe(&self) -> String {
+        "nginx responds over http".to_string()
+    }
+}
+
+pub struct NginxContainer {
+    container: RunningContainer,
+}
+
+impl NginxContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn base_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for NginxContainer {
+    fn from(container: RunningContainer) -> Self {
+        NginxContainer { container }
+    }
+}
+
+impl Deref for NginxContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpGetSucceeds,
+    modules::{ContainerModule, ModuleBuilder},
+};
+use std::{ops::Deref, sync::Arc};
+
+const HTTP_PORT: u16 = 11434;
+
+pub async fn ollama() -> GenericContainerBuilder {
+    ModuleBuilder::new(Ollama).into_builder()
+}
+
+// models run on the cpu unless the builder is given with_gpus(Gpus::All)
+pub struct Ollama;
+
+impl ContainerModule for Ollama {
+    type Container = OllamaContainer;
+
+    fn image(&self) -> String {
+        "ollama/ollama:0.1.48".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(HTTP_PORT, "/api/version")))
+    }
+}
+
+pub struct OllamaContainer {
+    container: RunningContainer,
+}
+
+impl OllamaContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn base_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    pub fn openai_base_url(&self) -> String {
+        format!("{}/v1", self.base_url())
+    }
+
+    // returns once the model is fully downloaded, which may take a while for large ones
+    pub async fn pull_model(&self, model: &str) -> Result<(), TestcontainersError> {
+        let result = self.container.exec(&["ollama", "pull", model]).await?;
+        if result.exit_code != 0 {
+            return Err(TestcontainersError::Provisioning(format!(
+                "unable to pull model {model}: {}",
+                result.stderr.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for OllamaContainer {
+    fn from(container: RunningContainer) -> Self {
+        OllamaContainer { container }
+    }
+}
+
+impl Deref for OllamaContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    docker_client::LogOptions,
+    modules::{env_vars, ContainerModule, ModuleBuilder},
+};
+use regex::Regex;
+use std::{ops::Deref, path::PathBuf};
+
+const LDAP_PORT: u16 = 1389;
+const LDIF_DIR: &str = "/ldifs";
+
+pub async fn openldap() -> GenericContainerBuilder {
+    ModuleBuilder::new(OpenLdap::default()).into_builder()
+}
+
+pub async fn openldap_with_ldif<P: Into<PathBuf>>(ldif_file: P) -> GenericContainerBuilder {
+    ModuleBuilder::new(OpenLdap::default().with_ldif(ldif_file)).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OpenLdap {
+    ldif_files: Vec<PathBuf>,
+}
+
+impl OpenLdap {
+    // the ldif files replace the default tree, so they have to create the root entry themselves
+    pub fn with_ldif<P: Into<PathBuf>>(mut self, ldif_file: P) -> Self {
+        self.ldif_files.push(ldif_file.into());
+        self
+    }
+}
+
+impl ContainerModule for OpenLdap {
+    type Container = OpenLdapContainer;
+
+    fn image(&self) -> String {
+        "bitnami/openldap:2.6".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        let mut env = env_vars(&[
+            ("LDAP_ROOT", "dc=example,dc=org"),
+            ("LDAP_ADMIN_USERNAME", "admin"),
+            ("LDAP_ADMIN_PASSWORD", "admin"),
+        ]);
+        if !self.ldif_files.is_empty() {
+            env.extend(env_vars(&[("LDAP_CUSTOM_LDIF_DIR", LDIF_DIR)]));
+        }
+        env
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![LDAP_PORT]
+    }
+
+    // the setup runs a temporary server before the final one is started
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::All(vec![
+            ReadyStrategy::LogMessageRegExp(
+                Regex::new(r"\*\* Starting slapd \*\*").unwrap(),
+                LogOptions::default(),
+            ),
+            ReadyStrategy::PortListening(format!("{LDAP_PORT}/tcp")),
+        ])
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        self.ldif_files.iter().fold(builder, |builder, ldif_file| {
+            let file_name = ldif_file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "seed.ldif".to_string());
+            builder.copy_file_to_container(ldif_file, format!("{LDIF_DIR}/{file_name}"))
+        })
+    }
+}
+
+pub struct OpenLdapContainer {
+    container: RunningContainer,
+}
+
+impl OpenLdapContainer {
+    pub fn base_dn(&self) -> &str {
+        self.container
+            .get_env("LDAP_ROOT")
+            .unwrap_or("dc=example,dc=org")
+    }
+
+    pub fn admin_dn(&self) -> String {
+        format!(
+            "cn={},{}",
+            self.container
+                .get_env("LDAP_ADMIN_USERNAME")
+                .unwrap_or("admin"),
+            self.base_dn()
+        )
+    }
+
+    pub fn admin_password(&self) -> &str {
+        self.container
+            .get_env("LDAP_ADMIN_PASSWORD")
+            .unwrap_or("adminpassword")
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{LDAP_PORT}/tcp"))
+    }
+
+    pub fn ldap_url(&self) -> String {
+        format!(
+            "ldap://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(LDAP_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for OpenLdapContainer {
+    fn from(container: RunningContainer) -> Self {
+        OpenLdapContainer { container }
+    }
+}
+
+impl Deref for OpenLdapContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,428 @@
+#[cfg(not(feature = "tokio-postgres"))]
+use crate::docker_client::LogOptions;
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    docker_client::ContainerClient,
+    error::TestcontainersError,
+    modules::{ContainerModule, ModuleBuilder},
+};
+use log::warn;
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+const POSTGRES_PORT: u16 = 5432;
+const INIT_DIR: &str = "/docker-entrypoint-initdb.d";
+
+pub async fn postgresql() -> GenericContainerBuilder {
+    ModuleBuilder::new(Postgres::default()).into_builder()
+}
+
+pub async fn timescaledb() -> GenericContainerBuilder {
+    ModuleBuilder::new(Postgres::timescaledb()).into_builder()
+}
+
+pub async fn pgvector() -> GenericContainerBuilder {
+    ModuleBuilder::new(Postgres::pgvector()).into_builder()
+}
+
+// strings are sql statements, paths are sql files read from the host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitSql {
+    Sql(String),
+    File(PathBuf),
+}
+
+impl From<&str> for InitSql {
+    fn from(sql: &str) -> Self {
+        InitSql::Sql(sql.to_string())
+    }
+}
+
+impl From<String> for InitSql {
+    fn from(sql: String) -> Self {
+        InitSql::Sql(sql)
+    }
+}
+
+impl From<&Path> for InitSql {
+    fn from(path: &Path) -> Self {
+        InitSql::File(path.to_path_buf())
+    }
+}
+
+impl From<PathBuf> for InitSql {
+    fn from(path: PathBuf) -> Self {
+        InitSql::File(path)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Postgres {
+    image: String,
+    extension: Option<String>,
+    database: String,
+    username: String,
+    password: String,
+    init_scripts: Vec<InitSql>,
+}
+
+impl Default for Postgres {
+    fn default() -> Self {
+        Postgres {
+            image: "postgres:latest".to_string(),
+            extension: None,
+            database: "test".to_string(),
+            username: "test".to_string(),
+            password: "test".to_string(),
+            init_scripts: vec![],
+        }
+    }
+}
+
+impl Postgres {
+    pub fn timescaledb() -> Self {
+        Postgres {
+            image: "timescale/timescaledb:latest-pg16".to_string(),
+            extension: Some("timescaledb".to_string()),
+            ..Postgres::default()
+        }
+    }
+
+    pub fn pgvector() -> Self {
+        Postgres {
+            image: "pgvector/pgvector:pg16".to_string(),
+            extension: Some("vector".to_string()),
+            ..Postgres::default()
+        }
+    }
+
+    pub fn with_database<S: Into<String>>(mut self, database: S) -> Self {
+        self.database = database.into();
+        self
+    }
+
+    pub fn with_username<S: Into<String>>(mut self, username: S) -> Self {
+        self.username = username.into();
+        self
+    }
+
+    pub fn with_password<S: Into<String>>(mut self, password: S) -> Self {
+        self.password = password.into();
+        self
+    }
+
+    // scripts run in the order they are added, on the first start only
+    pub fn with_init_sql<S: Into<InitSql>>(mut self, init_sql: S) -> Self {
+        self.init_scripts.push(init_sql.into());
+        self
+    }
+
+    fn validation_query(&self) -> String {
+        match &self.extension {
+            Some(extension) => format!("CREATE EXTENSION IF NOT EXISTS {extension}"),
+            None => "SELECT 1".to_string(),
+        }
+    }
+}
+
+impl ContainerModule for Postgres {
+    type Container = PostgresContainer;
+
+    fn image(&self) -> String {
+        self.image.clone()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        vec![
+            ("POSTGRES_DB".to_string(), self.database.clone()),
+            ("POSTGRES_USER".to_string(), self.username.clone()),
+            ("POSTGRES_PASSWORD".to_string(), self.password.clone()),
+        ]
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![POSTGRES_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        Some(["postgres", "-c", "fsync=off"].map(String::from).to_vec())
+    }
+
+    // the container is only ready once the extension is created in the test database, so an
+    // image that doesn't ship it never gets ready
+    #[cfg(feature = "tokio-postgres")]
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(std::sync::Arc::new(
+            crate::wait::PostgresConnection::new(
+                self.username.as_str(),
+                self.password.as_str(),
+                self.database.as_str(),
+            )
+            .with_validation_query(self.validation_query()),
+        ))
+    }
+
+    #[cfg(not(feature = "tokio-postgres"))]
+    fn wait_strategy(&self) -> ReadyStrategy {
+        // the entrypoint first starts a temporary server to run the init scripts
+        let ready = ReadyStrategy::LogMessageRegExp(
+            r"(?s).*database system is ready to accept connections.*\s.*database system is ready to accept connections.*\s"
+                .parse()
+                .unwrap(),
+            LogOptions::default(),
+        );
+        match &self.extension {
+            Some(_) => ReadyStrategy::All(vec![
+                ready,
+                ReadyStrategy::ExecCommand(vec![
+                    "psql".to_string(),
+                    "-U".to_string(),
+                    self.username.clone(),
+                    "-d".to_string(),
+                    self.database.clone(),
+                    "-c".to_string(),
+                    self.validation_query(),
+                ]),
+            ]),
+            None => ready,
+        }
+    }
+
+    // the entrypoint runs the scripts in alphabetical order
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        self.init_scripts
+            .iter()
+            .enumerate()
+            .fold(builder, |builder, (index, init_sql)| {
+                let container_path = format!("{INIT_DIR}/{index:03}-testcontainers.sql");
+                match init_sql {
+                    InitSql::Sql(sql) => {
+                        builder.with_copy_string_to_file(sql.as_str(), container_path)
+                    }
+                    InitSql::File(path) => builder.copy_file_to_container(path, container_path),
+                }
+            })
+    }
+}
+
+pub struct PostgresContainer {
+    container: RunningContainer,
+    cloning: tokio::sync::Mutex<()>,
+}
+
+impl PostgresContainer {
+    pub fn username(&self) -> &str {
+        self.container
+            .get_env("POSTGRES_USER")
+            .unwrap_or("postgres")
+    }
+
+    pub fn password(&self) -> &str {
+        self.container
+            .get_env("POSTGRES_PASSWORD")
+            .unwrap_or_default()
+    }
+
+    pub fn database(&self) -> &str {
+        self.container
+            .get_env("POSTGRES_DB")
+            .unwrap_or(self.username())
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{POSTGRES_PORT}/tcp"))
+    }
+
+    pub fn connection_string(&self) -> String {
+        format!(
+            "postgres://{}:{}@{}:{}/{}",
+            self.username(),
+            self.password(),
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(POSTGRES_PORT),
+            self.database()
+        )
+    }
+
+    #[cfg(feature = "tokio-postgres")]
+    pub fn tokio_postgres_config(&self) -> tokio_postgres::Config {
+        let mut config = tokio_postgres::Config::new();
+        config
+            .host(self.container.get_host())
+            .port(self.get_host_port().unwrap_or(POSTGRES_PORT))
+            .user(self.username())
+            .password(self.password())
+            .dbname(self.database());
+        config
+    }
+
+    // blocking, for the tests that don't use an async database stack
+    #[cfg(feature = "diesel-postgres")]
+    pub fn establish_diesel_connection(
+        &self,
+    ) -> Result<diesel::PgConnection, crate::error::TestcontainersError> {
+        super::diesel_connection::establish(&self.connection_string())
+    }
+
+    // migrations embedded with diesel_migrations::embed_migrations!, the ones already applied
+    // are skipped
+    #[cfg(feature = "diesel-postgres")]
+    pub fn run_diesel_migrations(
+        &self,
+        migrations: diesel_migrations::EmbeddedMigrations,
+    ) -> Result<(), crate::error::TestcontainersError> {
+        super::diesel_connection::run_pending_migrations::<diesel::PgConnection, _>(
+            &self.connection_string(),
+            migrations,
+        )
+    }
+
+    // a copy of the database created by the init scripts, which must not be connected to as
+    // long as databases are created from it
+    pub async fn create_database_from_template(
+        &self,
+        name: &str,
+    ) -> Result<TemplateDatabase, TestcontainersError> {
+        // cloning fails while another clone of the template is in progress
+        let _cloning = self.cloning.lock().await;
+        let client = self.container.client_handle();
+        psql(
+            &client,
+            self.username(),
+            &format!(
+                "CREATE DATABASE {} TEMPLATE {}",
+                quote_identifier(name),
+                quote_identifier(self.database())
+            ),
+        )
+        .await?;
+        Ok(TemplateDatabase {
+            name: name.to_string(),
+            connection_string: format!(
+                "postgres://{}:{}@{}:{}/{}",
+                self.username(),
+                self.password(),
+                self.container.get_host(),
+                self.get_host_port().unwrap_or(POSTGRES_PORT),
+                encode_path_segment(name)
+            ),
+            username: self.username().to_string(),
+            client: Some(client),
+        })
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+async fn psql(
+    client: &ContainerClient,
+    username: &str,
+    sql: &str,
+) -> Result<(), TestcontainersError> {
+    let command = [
+        "psql",
+        "-U",
+        username,
+        "-d",
+        "postgres",
+        "-v",
+        "ON_ERROR_STOP=1",
+        "-c",
+        sql,
+    ]
+    .map(String::from);
+    let result = client.exec(&command).await?;
+    if result.exit_code != 0 {
+        return Err(TestcontainersError::Provisioning(format!(
+            "{sql} failed: {}",
+            result.stderr.trim()
+        )));
+    }
+    Ok(())
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+// the database name is the path of the connection string
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+impl From<RunningContainer> for PostgresContainer {
+    fn from(container: RunningContainer) -> Self {
+        PostgresContainer {
+            container,
+            cloning: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+// dropping the handle drops the database in the background, which may not be done when the
+// test ends; remove().await is the reliable way to get rid of it
+pub struct TemplateDatabase {
+    name: String,
+    connection_string: String,
+    username: String,
+    client: Option<ContainerClient>,
+}
+
+impl TemplateDatabase {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn connection_string(&self) -> &str {
+        &self.connection_string
+    }
+
+    pub async fn remove(mut self) -> Result<(), TestcontainersError> {
+        match self.client.take() {
+            Some(client) => psql(&client, &self.username, &self.drop_statement()).await,
+            None => Ok(()),
+        }
+    }
+
+    fn drop_statement(&self) -> String {
+        format!(
+            "DROP DATABASE IF EXISTS {} WITH (FORCE)",
+            quote_identifier(&self.name)
+        )
+    }
+}
+
+impl Drop for TemplateDatabase {
+    fn drop(&mut self) {
+        let Some(client) = self.client.take() else {
+            return;
+        };
+        let username = std::mem::take(&mut self.username);
+        let statement = self.drop_statement();
+        crate::blocking::spawn(async move {
+            if let Err(error) = psql(&client, &username, &statement).await {
+                warn!("🐋 Unable to drop a template database: {error}");
+            }
+        });
+    }
+}
+
+impl Deref for PostgresContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::{HttpGetSucceeds, HttpRequest},
+    modules::{command_line, ContainerModule, ModuleBuilder},
+};
+use hyper::Method;
+use serde_json::json;
+use std::{ops::Deref, sync::Arc};
+
+const BROKER_PORT: u16 = 6650;
+const ADMIN_PORT: u16 = 8080;
+const CLUSTER: &str = "standalone";
+
+pub async fn pulsar() -> GenericContainerBuilder {
+    ModuleBuilder::new(Pulsar).into_builder()
+}
+
+pub struct Pulsar;
+
+impl ContainerModule for Pulsar {
+    type Container = PulsarContainer;
+
+    fn image(&self) -> String {
+        "apachepulsar/pulsar:3.2.2".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![BROKER_PORT, ADMIN_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        command_line(&["bin/pulsar", "standalone"])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(
+            ADMIN_PORT,
+            "/admin/v2/brokers/health",
+        )))
+    }
+}
+
+pub struct PulsarContainer {
+    container: RunningContainer,
+}
+
+impl PulsarContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{BROKER_PORT}/tcp"))
+    }
+
+    pub fn get_admin_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{ADMIN_PORT}/tcp"))
+    }
+
+    pub fn broker_url(&self) -> String {
+        format!(
+            "pulsar://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(BROKER_PORT)
+        )
+    }
+
+    pub fn admin_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_admin_host_port().unwrap_or(ADMIN_PORT)
+        )
+    }
+
+    pub async fn create_tenant(&self, tenant: &str) -> Result<(), TestcontainersError> {
+        self.put(
+            &format!("/admin/v2/tenants/{tenant}"),
+            Some(json!({ "allowedClusters": [CLUSTER] })),
+        )
+        .await
+    }
+
+    pub async fn create_namespace(
+        &self,
+        tenant: &str,
+        namespace: &str,
+    ) -> Result<(), TestcontainersError> {
+        self.put(&format!("/admin/v2/namespaces/{tenant}/{namespace}"), None)
+            .await
+    }
+
+    pub async fn create_topic(
+        &self,
+        tenant: &str,
+        namespace: &str,
+        topic: &str,
+    ) -> Result<(), TestcontainersError> {
+        self.put(
+            &format!("/admin/v2/persistent/{tenant}/{namespace}/{topic}"),
+            None,
+        )
+        .await
+    }
+
+    pub async fn create_partitioned_topic(
+        &self,
+        tenant: &str,
+        namespace: &str,
+        topic: &str,
+        partitions: u32,
+    ) -> Result<(), TestcontainersError> {
+        self.put(
+            &format!("/admin/v2/persistent/{tenant}/{namespace}/{topic}/partitions"),
+            Some(json!(partitions)),
+        )
+        .await
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+
+    async fn put(
+        &self,
+        path: &str,
+        json: Option<serde_json::Value>,
+    ) -> Result<(), TestcontainersError> {
+        let mut request = HttpRequest::new(Method::PUT, format!("{}{path}", self.admin_url()));
+        if let Some(json) = json {
+            request = request.with_json(json);
+        }
+        request.send_expecting_success().await?;
+        Ok(())
+    }
+}
+
+impl From<RunningContainer> for PulsarContainer {
+    fn from(container: RunningContainer) -> Self {
+        PulsarContainer { container }
+    }
+}
+
+impl Deref for PulsarContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpRequest,
+    modules::{env_vars, ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use containers_api::url::url::form_urlencoded::byte_serialize;
+use hyper::Method;
+use serde_json::json;
+use std::{ops::Deref, sync::Arc};
+
+const AMQP_PORT: u16 = 5672;
+const MANAGEMENT_PORT: u16 = 15672;
+
+pub async fn rabbitmq() -> GenericContainerBuilder {
+    ModuleBuilder::new(RabbitMq).into_builder()
+}
+
+pub struct RabbitMq;
+
+impl ContainerModule for RabbitMq {
+    type Container = RabbitMqContainer;
+
+    fn image(&self) -> String {
+        "rabbitmq:3-management".to_string()
+    }
+
+    // guest is only allowed to connect from localhost, which excludes port mappings
+    fn env(&self) -> Vec<(String, String)> {
+        env_vars(&[
+            ("RABBITMQ_DEFAULT_USER", "test"),
+            ("RABBITMQ_DEFAULT_PASS", "test"),
+        ])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![AMQP_PORT, MANAGEMENT_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ManagementApiReady))
+    }
+}
+
+struct ManagementApiReady;
+
+#[async_trait]
+impl WaitStrategy for ManagementApiReady {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container
+            .get_host_port(format!("{MANAGEMENT_PORT}/tcp"))
+            .await?
+        else {
+            return Ok(false);
+        };
+        // the credentials may have been overridden after the builder was created
+        let credentials = container
+            .exec(&[
+                "sh",
+                "-c",
+                "echo $RABBITMQ_DEFAULT_USER:$RABBITMQ_DEFAULT_PASS",
+            ])
+            .await?;
+        let (username, password) = credentials
+            .stdout
+            .trim()
+            .split_once(':')
+            .unwrap_or_default();
+        let request = HttpRequest::new(
+            Method::GET,
+            format!(
+                "http://{}:{port}/api/health/checks/alarms",
+                container.host()
+            ),
+        )
+        .with_basic_auth(username, password);
+        Ok(matches!(request.send().await, Ok((status, _)) if status.is_success()))
+    }
+
+    fn describe(&self) -> String {
+        "rabbitmq management API ready".to_string()
+    }
+}
+
+pub struct RabbitMqContainer {
+    container: RunningContainer,
+}
+
+impl RabbitMqContainer {
+    pub fn username(&self) -> &str {
+        self.container
+            .get_env("RABBITMQ_DEFAULT_USER")
+            .unwrap_or("guest")
+    }
+
+    pub fn password(&self) -> &str {
+        self.container
+            .get_env("RABBITMQ_DEFAULT_PASS")
+            .unwrap_or("guest")
+    }
+
+    pub fn amqp_url(&self) -> String {
+        format!(
+            "amqp://{}:{}@{}:{}",
+            self.username(),
+            self.password(),
+            self.container.get_host(),
+            self.host_port(AMQP_PORT)
+        )
+    }
+
+    pub fn management_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.host_port(MANAGEMENT_PORT)
+        )
+    }
+
+    pub async fn declare_vhost(&self, vhost: &str) -> Result<(), TestcontainersError> {
+        self.put(&format!("/api/vhosts/{}", encode(vhost)), json!({}))
+            .await
+    }
+
+    // tags are comma separated, e.g. administrator or management
+    pub async fn declare_user(
+        &self,
+        username: &str,
+        password: &str,
+        tags: &str,
+    ) -> Result<(), TestcontainersError> {
+        self.put(
+            &format!("/api/users/{}", encode(username)),
+            json!({ "password": password, "tags": tags }),
+        )
+        .await
+    }
+
+    pub async fn grant_permissions(
+        &self,
+        vhost: &str,
+        username: &str,
+    ) -> Result<(), TestcontainersError> {
+        self.put(
+            &format!("/api/permissions/{}/{}", encode(vhost), encode(username)),
+            json!({ "configure": ".*", "write": ".*", "read": ".*" }),
+        )
+        .await
+    }
+
+    pub async fn declare_exchange(
+        &self,
+        vhost: &str,
+        exchange: &str,
+        exchange_type: &str,
+    ) -> Result<(), TestcontainersError> {
+        self.put(
+            &format!("/api/exchanges/{}/{}", encode(vhost), encode(exchange)),
+            json!({ "type": exchange_type, "durable": true }),
+        )
+        .await
+    }
+
+    pub async fn declare_queue(&self, vhost: &str, queue: &str) -> Result<(), TestcontainersError> {
+        self.put(
+            &format!("/api/queues/{}/{}", encode(vhost), encode(queue)),
+            json!({ "durable": true }),
+        )
+        .await
+    }
+
+    pub async fn declare_binding(
+        &self,
+        vhost: &str,
+        exchange: &str,
+        queue: &str,
+        routing_key: &str,
+    ) -> Result<(), TestcontainersError> {
+        HttpRequest::new(
+            Method::POST,
+            format!(
+                "{}/api/bindings/{}/e/{}/q/{}",
+                self.management_url(),
+                encode(vhost),
+                encode(exchange),
+                encode(queue)
+            ),
+        )
+        .with_basic_auth(self.username(), self.password())
+        .with_json(json!({ "routing_key": routing_key }))
+        .send_expecting_success()
+        .await?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+
+    fn host_port(&self, port: u16) -> u16 {
+        self.container
+            .get_host_port(format!("{port}/tcp"))
+            .unwrap_or(port)
+    }
+
+    async fn put(&self, path: &str, json: serde_json::Value) -> Result<(), TestcontainersError> {
+        HttpRequest::new(Method::PUT, format!("{}{path}", self.management_url()))
+            .with_basic_auth(self.username(), self.password())
+            .with_json(json)
+            .send_expecting_success()
+            .await?;
+        Ok(())
+    }
+}
+
+// the default vhost is named /
+fn encode(segment: &str) -> String {
+    byte_serialize(segment.as_bytes()).collect()
+}
+
+impl From<RunningContainer> for RabbitMqContainer {
+    fn from(container: RunningContainer) -> Self {
+        RabbitMqContainer { container }
+    }
+}
+
+impl Deref for RabbitMqContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
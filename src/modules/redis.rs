@@ -0,0 +1,103 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    modules::{command_line, ContainerModule, ModuleBuilder},
+};
+use std::ops::Deref;
+
+const REDIS_PORT: u16 = 6379;
+// the password is read from REDIS_PASSWORD at runtime so that add_env is enough to set it
+const PING: &str =
+    r#"redis-cli ${REDIS_PASSWORD:+-a "$REDIS_PASSWORD" --no-auth-warning} ping | grep -q PONG"#;
+
+pub async fn redis() -> GenericContainerBuilder {
+    ModuleBuilder::new(Redis::default()).into_builder()
+}
+
+pub async fn redis_stack() -> GenericContainerBuilder {
+    ModuleBuilder::new(Redis::stack()).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Redis {
+    stack: bool,
+}
+
+impl Redis {
+    // redis with the search, json, time series and bloom modules
+    pub fn stack() -> Self {
+        Redis { stack: true }
+    }
+}
+
+impl ContainerModule for Redis {
+    type Container = RedisContainer;
+
+    fn image(&self) -> String {
+        match self.stack {
+            true => "redis/redis-stack-server:latest".to_string(),
+            false => "redis:7".to_string(),
+        }
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![REDIS_PORT]
+    }
+
+    fn command(&self) -> Option<Vec<String>> {
+        let script = match self.stack {
+            true => {
+                r#"REDIS_ARGS="$REDIS_ARGS ${REDIS_PASSWORD:+--requirepass $REDIS_PASSWORD}" exec /entrypoint.sh"#
+            }
+            false => r#"exec redis-server ${REDIS_PASSWORD:+--requirepass "$REDIS_PASSWORD"}"#,
+        };
+        command_line(&["sh", "-c", script])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::ExecCommand(["sh", "-c", PING].map(String::from).to_vec())
+    }
+}
+
+pub struct RedisContainer {
+    container: RunningContainer,
+}
+
+impl RedisContainer {
+    pub fn password(&self) -> Option<&str> {
+        self.container.get_env("REDIS_PASSWORD")
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{REDIS_PORT}/tcp"))
+    }
+
+    pub fn connection_url(&self) -> String {
+        let credentials = self
+            .password()
+            .map(|password| format!(":{password}@"))
+            .unwrap_or_default();
+        format!(
+            "redis://{credentials}{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(REDIS_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for RedisContainer {
+    fn from(container: RunningContainer) -> Self {
+        RedisContainer { container }
+    }
+}
+
+impl Deref for RedisContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
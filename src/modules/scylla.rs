@@ -0,0 +1,81 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    modules::{command_line, ContainerModule, ModuleBuilder},
+};
+use std::ops::Deref;
+
+const CQL_PORT: u16 = 9042;
+
+pub async fn scylla() -> GenericContainerBuilder {
+    ModuleBuilder::new(Scylla).into_builder()
+}
+
+pub struct Scylla;
+
+impl ContainerModule for Scylla {
+    type Container = ScyllaContainer;
+
+    fn image(&self) -> String {
+        "scylladb/scylla:5.4".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![CQL_PORT]
+    }
+
+    // developer mode skips the io tuning and hardware checks, a single shard keeps startup short
+    fn command(&self) -> Option<Vec<String>> {
+        command_line(&[
+            "--developer-mode=1",
+            "--smp=1",
+            "--memory=512M",
+            "--overprovisioned=1",
+            "--skip-wait-for-gossip-to-settle=0",
+        ])
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::PortListening(format!("{CQL_PORT}/tcp"))
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        builder.with_start_timeout("120s")
+    }
+}
+
+pub struct ScyllaContainer {
+    container: RunningContainer,
+}
+
+impl ScyllaContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{CQL_PORT}/tcp"))
+    }
+
+    // in the host:port form SessionBuilder::known_node expects
+    pub fn contact_point(&self) -> String {
+        format!(
+            "{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(CQL_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for ScyllaContainer {
+    fn from(container: RunningContainer) -> Self {
+        ScyllaContainer { container }
+    }
+}
+
+impl Deref for ScyllaContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
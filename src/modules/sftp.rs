@@ -0,0 +1,141 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    docker_client::LogOptions,
+    error::TestcontainersError,
+    modules::{env_vars, ContainerModule, ModuleBuilder},
+};
+use std::ops::Deref;
+
+const SSH_PORT: u16 = 22;
+const UPLOAD_DIR: &str = "upload";
+const HOST_KEY: &str = "/etc/ssh/ssh_host_ed25519_key.pub";
+
+pub async fn sftp_server(username: &str, password: &str) -> GenericContainerBuilder {
+    ModuleBuilder::new(Sftp::new(username, password)).into_builder()
+}
+
+pub async fn sftp_server_with_key(username: &str, public_key: &str) -> GenericContainerBuilder {
+    ModuleBuilder::new(Sftp::new_with_key(username, public_key)).into_builder()
+}
+
+// users are chrooted in their home, which is not writable, so they get an upload directory
+#[derive(Debug, Clone)]
+pub struct Sftp {
+    username: String,
+    password: String,
+    public_key: Option<String>,
+}
+
+impl Sftp {
+    pub fn new<S: Into<String>>(username: S, password: S) -> Self {
+        Sftp {
+            username: username.into(),
+            password: password.into(),
+            public_key: None,
+        }
+    }
+
+    pub fn new_with_key<S: Into<String>>(username: S, public_key: S) -> Self {
+        Sftp {
+            username: username.into(),
+            password: String::new(),
+            public_key: Some(public_key.into()),
+        }
+    }
+}
+
+impl ContainerModule for Sftp {
+    type Container = SftpContainer;
+
+    fn image(&self) -> String {
+        "atmoz/sftp:alpine".to_string()
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        let user = format!("{}:{}:1001::{UPLOAD_DIR}", self.username, self.password);
+        env_vars(&[("SFTP_USERS", &user)])
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![SSH_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::LogMessageRegExp(
+            format!("Server listening on .* port {SSH_PORT}")
+                .parse()
+                .unwrap(),
+            LogOptions::default(),
+        )
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        match &self.public_key {
+            Some(public_key) => builder.with_copy_string_to_file(
+                public_key.as_str(),
+                format!("/home/{}/.ssh/keys/id.pub", self.username),
+            ),
+            None => builder,
+        }
+    }
+}
+
+pub struct SftpContainer {
+    container: RunningContainer,
+}
+
+impl SftpContainer {
+    pub fn username(&self) -> &str {
+        self.user_field(0)
+    }
+
+    // empty when the user authenticates with a key
+    pub fn password(&self) -> &str {
+        self.user_field(1)
+    }
+
+    pub fn upload_dir(&self) -> String {
+        format!("/{UPLOAD_DIR}")
+    }
+
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{SSH_PORT}/tcp"))
+    }
+
+    // e.g. to populate a known_hosts file, in the `ssh-ed25519 AAAA...` format
+    pub async fn host_public_key(&self) -> Result<String, TestcontainersError> {
+        let result = self.container.exec(&["cat", HOST_KEY]).await?;
+        if result.exit_code != 0 {
+            return Err(TestcontainersError::Provisioning(format!(
+                "unable to read {HOST_KEY}: {}",
+                result.stderr.trim()
+            )));
+        }
+        Ok(result.stdout.trim().to_string())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+
+    fn user_field(&self, index: usize) -> &str {
+        self.container
+            .get_env("SFTP_USERS")
+            .and_then(|user| user.split(':').nth(index))
+            .unwrap_or_default()
+    }
+}
+
+impl From<RunningContainer> for SftpContainer {
+    fn from(container: RunningContainer) -> Self {
+        SftpContainer { container }
+    }
+}
+
+impl Deref for SftpContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
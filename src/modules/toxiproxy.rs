@@ -0,0 +1,239 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::{HttpGetSucceeds, HttpRequest},
+    modules::{ContainerModule, ModuleBuilder},
+    port::Protocol,
+};
+use hyper::Method;
+use serde_json::json;
+use std::{ops::Deref, sync::Arc, time::Duration};
+
+const API_PORT: u16 = 8474;
+const FIRST_PROXY_PORT: u16 = 8666;
+const PROXY_PORTS: u16 = 32;
+
+pub async fn toxiproxy() -> GenericContainerBuilder {
+    ModuleBuilder::new(Toxiproxy).into_builder()
+}
+
+pub struct Toxiproxy;
+
+impl ContainerModule for Toxiproxy {
+    type Container = ToxiproxyContainer;
+
+    fn image(&self) -> String {
+        "ghcr.io/shopify/toxiproxy:2.9.0".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![API_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(API_PORT, "/version")))
+    }
+
+    // proxies listen on ports that must be exposed before the container starts, so a fixed
+    // range is exposed upfront
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        builder.add_exposed_port_range(
+            FIRST_PROXY_PORT..=FIRST_PROXY_PORT + PROXY_PORTS - 1,
+            Protocol::Tcp,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToxicDirection {
+    Upstream,
+    Downstream,
+}
+
+impl ToxicDirection {
+    fn name(&self) -> &'static str {
+        match self {
+            ToxicDirection::Upstream => "upstream",
+            ToxicDirection::Downstream => "downstream",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Toxic {
+    Latency { latency: Duration, jitter: Duration },
+    Bandwidth { rate_kb_per_second: u64 },
+    ResetPeer { timeout: Duration },
+    Timeout { timeout: Duration },
+}
+
+impl Toxic {
+    fn kind(&self) -> &'static str {
+        match self {
+            Toxic::Latency { .. } => "latency",
+            Toxic::Bandwidth { .. } => "bandwidth",
+            Toxic::ResetPeer { .. } => "reset_peer",
+            Toxic::Timeout { .. } => "timeout",
+        }
+    }
+
+    fn attributes(&self) -> serde_json::Value {
+        match self {
+            Toxic::Latency { latency, jitter } => json!({
+                "latency": latency.as_millis() as u64,
+                "jitter": jitter.as_millis() as u64,
+            }),
+            Toxic::Bandwidth { rate_kb_per_second } => json!({ "rate": rate_kb_per_second }),
+            Toxic::ResetPeer { timeout } | Toxic::Timeout { timeout } => {
+                json!({ "timeout": timeout.as_millis() as u64 })
+            }
+        }
+    }
+}
+
+pub struct ToxiproxyContainer {
+    container: RunningContainer,
+}
+
+impl ToxiproxyContainer {
+    pub fn api_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.container
+                .get_host_port(format!("{API_PORT}/tcp"))
+                .unwrap_or(API_PORT)
+        )
+    }
+
+    // upstream is resolved from within the toxiproxy container, e.g. a network alias and port
+    pub async fn create_proxy(
+        &self,
+        name: &str,
+        upstream: &str,
+    ) -> Result<Proxy, TestcontainersError> {
+        let proxies = HttpRequest::new(Method::GET, format!("{}/proxies", self.api_url()))
+            .send_expecting_success()
+            .await?;
+        let proxies: serde_json::Value = serde_json::from_str(&proxies)
+            .map_err(|error| TestcontainersError::Http(error.to_string()))?;
+        let used_ports: Vec<u16> = proxies
+            .as_object()
+            .into_iter()
+            .flat_map(|proxies| proxies.values())
+            .filter_map(|proxy| proxy["listen"].as_str()?.rsplit(':').next()?.parse().ok())
+            .collect();
+        let listen_port = (FIRST_PROXY_PORT..FIRST_PROXY_PORT + PROXY_PORTS)
+            .find(|port| !used_ports.contains(port))
+            .ok_or_else(|| {
+                TestcontainersError::Provisioning(format!(
+                    "all the {PROXY_PORTS} toxiproxy ports are already in use"
+                ))
+            })?;
+        HttpRequest::new(Method::POST, format!("{}/proxies", self.api_url()))
+            .with_json(json!({
+                "name": name,
+                "listen": format!("0.0.0.0:{listen_port}"),
+                "upstream": upstream,
+                "enabled": true,
+            }))
+            .send_expecting_success()
+            .await?;
+        Ok(Proxy {
+            name: name.to_string(),
+            proxy_url: format!("{}/proxies/{name}", self.api_url()),
+            host: self.container.get_host(),
+            host_port: self
+                .container
+                .get_host_port(format!("{listen_port}/tcp"))
+                .unwrap_or(listen_port),
+            listen_port,
+        })
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for ToxiproxyContainer {
+    fn from(container: RunningContainer) -> Self {
+        ToxiproxyContainer { container }
+    }
+}
+
+impl Deref for ToxiproxyContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    name: String,
+    proxy_url: String,
+    host: String,
+    host_port: u16,
+    listen_port: u16,
+}
+
+impl Proxy {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    // where the test code connects to
+    pub fn host_port(&self) -> u16 {
+        self.host_port
+    }
+
+    // where other containers of the network connect to
+    pub fn listen_port(&self) -> u16 {
+        self.listen_port
+    }
+
+    // toxics are named after their type and direction, which is what remove_toxic expects
+    pub async fn add_toxic(
+        &self,
+        toxic: &Toxic,
+        direction: ToxicDirection,
+    ) -> Result<String, TestcontainersError> {
+        let toxic_name = format!("{}_{}", toxic.kind(), direction.name());
+        HttpRequest::new(Method::POST, format!("{}/toxics", self.proxy_url))
+            .with_json(json!({
+                "name": toxic_name,
+                "type": toxic.kind(),
+                "stream": direction.name(),
+                "toxicity": 1.0,
+                "attributes": toxic.attributes(),
+            }))
+            .send_expecting_success()
+            .await?;
+        Ok(toxic_name)
+    }
+
+    pub async fn remove_toxic(&self, toxic_name: &str) -> Result<(), TestcontainersError> {
+        HttpRequest::new(
+            Method::DELETE,
+            format!("{}/toxics/{toxic_name}", self.proxy_url),
+        )
+        .send_expecting_success()
+        .await?;
+        Ok(())
+    }
+
+    // a disabled proxy closes all its connections and refuses new ones
+    pub async fn set_enabled(&self, enabled: bool) -> Result<(), TestcontainersError> {
+        HttpRequest::new(Method::POST, self.proxy_url.clone())
+            .with_json(json!({ "enabled": enabled }))
+            .send_expecting_success()
+            .await?;
+        Ok(())
+    }
+}
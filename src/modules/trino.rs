@@ -0,0 +1,146 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::HttpRequest,
+    modules::{ContainerModule, ModuleBuilder},
+    wait::{async_trait, ContainerView, WaitStrategy},
+};
+use hyper::Method;
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+const HTTP_PORT: u16 = 8080;
+const CATALOG_DIR: &str = "/etc/trino/catalog";
+
+pub async fn trino() -> GenericContainerBuilder {
+    ModuleBuilder::new(Trino::default()).into_builder()
+}
+
+pub async fn trino_with_catalogs<P: AsRef<Path>>(
+    catalogs: &[(&str, P)],
+) -> GenericContainerBuilder {
+    let trino = catalogs
+        .iter()
+        .fold(Trino::default(), |trino, (name, properties_file)| {
+            trino.with_catalog(*name, properties_file.as_ref())
+        });
+    ModuleBuilder::new(trino).into_builder()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Trino {
+    catalogs: Vec<(String, PathBuf)>,
+}
+
+impl Trino {
+    // the properties file becomes the catalog named after it, e.g. postgresql for
+    // ("postgresql", "tests/postgresql.properties")
+    pub fn with_catalog<S: Into<String>, P: Into<PathBuf>>(
+        mut self,
+        name: S,
+        properties_file: P,
+    ) -> Self {
+        self.catalogs.push((name.into(), properties_file.into()));
+        self
+    }
+}
+
+impl ContainerModule for Trino {
+    type Container = TrinoContainer;
+
+    fn image(&self) -> String {
+        "trinodb/trino:445".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ServerStarted))
+    }
+
+    fn customize(&self, builder: GenericContainerBuilder) -> GenericContainerBuilder {
+        self.catalogs.iter().fold(
+            builder.with_start_timeout("120s"),
+            |builder, (name, properties_file)| {
+                builder.copy_file_to_container(
+                    properties_file,
+                    format!("{CATALOG_DIR}/{name}.properties"),
+                )
+            },
+        )
+    }
+}
+
+// the http server is up before the coordinator accepts queries
+struct ServerStarted;
+
+#[async_trait]
+impl WaitStrategy for ServerStarted {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(port) = container.get_host_port(format!("{HTTP_PORT}/tcp")).await? else {
+            return Ok(false);
+        };
+        let url = format!("http://{}:{port}/v1/info", container.host());
+        let Ok((status, body)) = HttpRequest::new(Method::GET, url).send().await else {
+            return Ok(false);
+        };
+        let Ok(info) = serde_json::from_str::<serde_json::Value>(&body) else {
+            return Ok(false);
+        };
+        Ok(status.is_success() && info["starting"] == false)
+    }
+
+    fn describe(&self) -> String {
+        "trino server started".to_string()
+    }
+}
+
+pub struct TrinoContainer {
+    container: RunningContainer,
+}
+
+impl TrinoContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn http_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    // authentication is disabled, any user name is accepted
+    pub fn jdbc_url(&self) -> String {
+        format!(
+            "jdbc:trino://{}:{}?user=test",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for TrinoContainer {
+    fn from(container: RunningContainer) -> Self {
+        TrinoContainer { container }
+    }
+}
+
+impl Deref for TrinoContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
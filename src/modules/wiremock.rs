@@ -0,0 +1,115 @@
+use crate::{
+    container::{GenericContainerBuilder, ReadyStrategy, RunningContainer},
+    error::TestcontainersError,
+    http::{HttpGetSucceeds, HttpRequest},
+    modules::{ContainerModule, ModuleBuilder},
+};
+use hyper::Method;
+use serde_json::json;
+use std::{ops::Deref, sync::Arc};
+
+const HTTP_PORT: u16 = 8080;
+
+pub async fn wiremock() -> GenericContainerBuilder {
+    ModuleBuilder::new(WireMock).into_builder()
+}
+
+pub struct WireMock;
+
+impl ContainerModule for WireMock {
+    type Container = WireMockContainer;
+
+    fn image(&self) -> String {
+        "wiremock/wiremock:3.5.4".to_string()
+    }
+
+    fn exposed_ports(&self) -> Vec<u16> {
+        vec![HTTP_PORT]
+    }
+
+    fn wait_strategy(&self) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(HttpGetSucceeds::new(
+            HTTP_PORT,
+            "/__admin/mappings",
+        )))
+    }
+}
+
+pub struct WireMockContainer {
+    container: RunningContainer,
+}
+
+impl WireMockContainer {
+    pub fn get_host_port(&self) -> Option<u16> {
+        self.container.get_host_port(format!("{HTTP_PORT}/tcp"))
+    }
+
+    pub fn base_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.container.get_host(),
+            self.get_host_port().unwrap_or(HTTP_PORT)
+        )
+    }
+
+    pub fn admin_url(&self) -> String {
+        format!("{}/__admin", self.base_url())
+    }
+
+    // the mapping uses the wiremock json format, the id of the created stub is returned
+    pub async fn register_stub(
+        &self,
+        mapping: serde_json::Value,
+    ) -> Result<String, TestcontainersError> {
+        let body = HttpRequest::new(Method::POST, format!("{}/mappings", self.admin_url()))
+            .with_json(mapping)
+            .send_expecting_success()
+            .await?;
+        let created: serde_json::Value = serde_json::from_str(&body)
+            .map_err(|error| TestcontainersError::Http(error.to_string()))?;
+        Ok(created["id"].as_str().unwrap_or_default().to_string())
+    }
+
+    pub async fn stub_json_response(
+        &self,
+        method: &str,
+        url_path: &str,
+        status: u16,
+        body: serde_json::Value,
+    ) -> Result<String, TestcontainersError> {
+        self.register_stub(json!({
+            "request": { "method": method, "urlPath": url_path },
+            "response": {
+                "status": status,
+                "jsonBody": body,
+                "headers": { "Content-Type": "application/json" }
+            }
+        }))
+        .await
+    }
+
+    pub async fn reset(&self) -> Result<(), TestcontainersError> {
+        HttpRequest::new(Method::POST, format!("{}/reset", self.admin_url()))
+            .send_expecting_success()
+            .await?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> RunningContainer {
+        self.container
+    }
+}
+
+impl From<RunningContainer> for WireMockContainer {
+    fn from(container: RunningContainer) -> Self {
+        WireMockContainer { container }
+    }
+}
+
+impl Deref for WireMockContainer {
+    type Target = RunningContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindMode {
+    ReadOnly,
+    ReadWrite,
+}
+
+// relabels the host files for SELinux, z when several containers share them, Z otherwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelinuxLabel {
+    Shared,
+    Private,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindPropagation {
+    Private,
+    RPrivate,
+    Shared,
+    RShared,
+    Slave,
+    RSlave,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BindMount {
+    host_path: PathBuf,
+    container_path: String,
+    mode: BindMode,
+    selinux_label: Option<SelinuxLabel>,
+    propagation: Option<BindPropagation>,
+}
+
+impl BindMount {
+    pub fn new<P: Into<PathBuf>, S: Into<String>>(
+        host_path: P,
+        container_path: S,
+        mode: BindMode,
+    ) -> Self {
+        BindMount {
+            host_path: host_path.into(),
+            container_path: container_path.into(),
+            mode,
+            selinux_label: None,
+            propagation: None,
+        }
+    }
+
+    pub fn with_selinux_label(mut self, label: SelinuxLabel) -> Self {
+        self.selinux_label = Some(label);
+        self
+    }
+
+    pub fn with_propagation(mut self, propagation: BindPropagation) -> Self {
+        self.propagation = Some(propagation);
+        self
+    }
+
+    pub fn host_path(&self) -> &Path {
+        &self.host_path
+    }
+
+    // the HostConfig.Binds syntax, host paths have to be absolute
+    pub(crate) fn to_bind(&self) -> std::io::Result<String> {
+        let host_path = std::path::absolute(&self.host_path)?;
+        Ok(format!(
+            "{}:{}:{}",
+            host_path.display(),
+            self.container_path,
+            self.options()
+        ))
+    }
+
+    fn options(&self) -> String {
+        let mode = match self.mode {
+            BindMode::ReadOnly => "ro",
+            BindMode::ReadWrite => "rw",
+        };
+        let selinux_label = self.selinux_label.map(|label| match label {
+            SelinuxLabel::Shared => "z",
+            SelinuxLabel::Private => "Z",
+        });
+        let propagation = self.propagation.map(|propagation| match propagation {
+            BindPropagation::Private => "private",
+            BindPropagation::RPrivate => "rprivate",
+            BindPropagation::Shared => "shared",
+            BindPropagation::RShared => "rshared",
+            BindPropagation::Slave => "slave",
+            BindPropagation::RSlave => "rslave",
+        });
+        [Some(mode), selinux_label, propagation]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bind_mounts_are_formatted_with_their_options() {
+        let bind = BindMount::new("/data", "/var/lib/data", BindMode::ReadOnly);
+        assert_eq!(bind.to_bind().unwrap(), "/data:/var/lib/data:ro");
+        let bind = BindMount::new("/data", "/var/lib/data", BindMode::ReadWrite)
+            .with_selinux_label(SelinuxLabel::Private)
+            .with_propagation(BindPropagation::RShared);
+        assert_eq!(bind.to_bind().unwrap(), "/data:/var/lib/data:rw,Z,rshared");
+    }
+
+    #[test]
+    fn relative_host_paths_are_made_absolute() {
+        let bind = BindMount::new("data", "/data", BindMode::ReadWrite);
+        let expected = std::env::current_dir().unwrap().join("data");
+        assert_eq!(
+            bind.to_bind().unwrap(),
+            format!("{}:/data:rw", expected.display())
+        );
+    }
+}
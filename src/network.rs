@@ -0,0 +1,170 @@
+use crate::{
+    container::{GenericContainer, ReadyStrategy, RunningContainer},
+    docker_client::DockerClient,
+    error::TestcontainersError,
+    reaper,
+    runtime::ContainerRuntime,
+    session::Session,
+};
+use docker_api::{models, opts::NetworkCreateOpts};
+use std::sync::Arc;
+
+// the name containers use to reach the ports exposed with Network::expose_host_ports
+pub const HOST_ALIAS: &str = "host.testcontainers.internal";
+const DOCKER_HOST_ALIAS: &str = "host.docker.internal";
+
+pub struct Network {
+    name: String,
+    inner_network: docker_api::Network,
+    runtime: Arc<dyn ContainerRuntime>,
+}
+
+impl Network {
+    pub async fn create<S: Into<String>>(name: S) -> Result<Network, TestcontainersError> {
+        Self::create_on(DockerClient::shared(), name).await
+    }
+
+    // for containers created with_docker_host or with_runtime, which only see the networks of
+    // their own daemon
+    pub async fn create_on<S: Into<String>>(
+        runtime: Arc<dyn ContainerRuntime>,
+        name: S,
+    ) -> Result<Network, TestcontainersError> {
+        Self::create_with_subnets_on(runtime, name, &[]).await
+    }
+
+    pub async fn create_with_subnets<S: Into<String>>(
+        name: S,
+        subnets: &[&str],
+    ) -> Result<Network, TestcontainersError> {
+        Self::create_with_subnets_on(DockerClient::shared(), name, subnets).await
+    }
+
+    // containers can only be given a static address on a network with user defined subnets,
+    // IPv6 is enabled along with an IPv6 subnet
+    pub async fn create_with_subnets_on<S: Into<String>>(
+        runtime: Arc<dyn ContainerRuntime>,
+        name: S,
+        subnets: &[&str],
+    ) -> Result<Network, TestcontainersError> {
+        let name: String = name.into();
+        reaper::ensure_started(runtime.clone()).await?;
+        let labels = Session::current().labels();
+        let mut opts = NetworkCreateOpts::builder(&name)
+            .driver("bridge")
+            .check_duplicate(true)
+            .labels(labels);
+        if !subnets.is_empty() {
+            let config = subnets
+                .iter()
+                .map(|subnet| models::IpamConfig {
+                    auxiliary_addresses: None,
+                    gateway: None,
+                    ip_range: None,
+                    subnet: Some(subnet.to_string()),
+                })
+                .collect();
+            opts = opts
+                .enable_ipv6(subnets.iter().any(|subnet| subnet.contains(':')))
+                .ipam(models::Ipam {
+                    config: Some(config),
+                    driver: None,
+                    options: None,
+                });
+        }
+        let inner_network = runtime.create_network(&opts.build()).await?;
+        println!("🐋 Network {name} created");
+        Ok(Network {
+            name,
+            inner_network,
+            runtime,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn id(&self) -> &str {
+        self.inner_network.id().as_ref()
+    }
+
+    pub async fn inspect(&self) -> Result<models::Network, TestcontainersError> {
+        Ok(self.inner_network.inspect().await?)
+    }
+
+    pub async fn expose_host_port(
+        &self,
+        local_port: u16,
+    ) -> Result<ExposedHostPorts, TestcontainersError> {
+        self.expose_host_ports(&[local_port]).await
+    }
+
+    // servers of the test process become reachable at HOST_ALIAS:<port> from this network, through
+    // a socat sidecar relaying to the docker host gateway: the docker daemon has to run on the
+    // machine of the tests and the servers have to listen on every interface, not only loopback
+    pub async fn expose_host_ports(
+        &self,
+        local_ports: &[u16],
+    ) -> Result<ExposedHostPorts, TestcontainersError> {
+        let relays: Vec<String> = local_ports
+            .iter()
+            .map(|port| {
+                format!("socat TCP-LISTEN:{port},fork,reuseaddr TCP:{DOCKER_HOST_ALIAS}:{port} &")
+            })
+            .collect();
+        let script = format!("{} wait", relays.join(" "));
+        let builder = GenericContainer::from_image("alpine/socat:1.8.0.0")
+            .with_runtime(self.runtime.clone())
+            .with_entrypoint(&["/bin/sh", "-c"])
+            .with_command(&[&script])
+            .with_extra_host(DOCKER_HOST_ALIAS, "host-gateway")
+            .with_network(self)
+            .with_network_alias(HOST_ALIAS)
+            .with_wait_strategy_on_startup(ReadyStrategy::All(
+                local_ports
+                    .iter()
+                    .map(|port| relay_listening(*port))
+                    .collect(),
+            ));
+        let container = builder.create().await?.start().await?;
+        Ok(ExposedHostPorts {
+            container,
+            ports: local_ports.to_vec(),
+        })
+    }
+
+    pub async fn remove(self) -> Result<(), TestcontainersError> {
+        self.inner_network.delete().await?;
+        println!("🐋 Network {} removed", self.name);
+        Ok(())
+    }
+}
+
+// probing the port would open a connection through the relay to the server of the test process,
+// so the listening sockets are looked up in the container instead
+fn relay_listening(port: u16) -> ReadyStrategy {
+    let listening =
+        format!("cat /proc/net/tcp /proc/net/tcp6 2>/dev/null | grep -qE ':{port:04X} [0:]+ 0A '");
+    ReadyStrategy::ExecCommand(["sh", "-c", listening.as_str()].map(String::from).to_vec())
+}
+
+// the relay lives until stopped, or until the reaper removes it with the rest of the session
+pub struct ExposedHostPorts {
+    container: RunningContainer,
+    ports: Vec<u16>,
+}
+
+impl ExposedHostPorts {
+    pub fn host(&self) -> &str {
+        HOST_ALIAS
+    }
+
+    pub fn ports(&self) -> &[u16] {
+        &self.ports
+    }
+
+    pub async fn stop(self) -> Result<(), TestcontainersError> {
+        self.container.stop_and_remove().await
+    }
+}
@@ -0,0 +1,114 @@
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+    Sctp,
+}
+
+// converts into the "5432/tcp" specs accepted by the builder and get_host_port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContainerPort {
+    pub number: u16,
+    pub protocol: Protocol,
+}
+
+impl ContainerPort {
+    pub fn tcp(number: u16) -> Self {
+        ContainerPort {
+            number,
+            protocol: Protocol::Tcp,
+        }
+    }
+
+    pub fn udp(number: u16) -> Self {
+        ContainerPort {
+            number,
+            protocol: Protocol::Udp,
+        }
+    }
+
+    pub fn sctp(number: u16) -> Self {
+        ContainerPort {
+            number,
+            protocol: Protocol::Sctp,
+        }
+    }
+}
+
+impl Display for ContainerPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let protocol = match self.protocol {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+            Protocol::Sctp => "sctp",
+        };
+        write!(f, "{}/{protocol}", self.number)
+    }
+}
+
+impl From<u16> for ContainerPort {
+    fn from(number: u16) -> Self {
+        ContainerPort::tcp(number)
+    }
+}
+
+impl From<ContainerPort> for String {
+    fn from(port: ContainerPort) -> Self {
+        port.to_string()
+    }
+}
+
+// the protocol defaults to tcp like with docker run -p
+impl FromStr for ContainerPort {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let (number, protocol) = spec.split_once('/').unwrap_or((spec, "tcp"));
+        let number = number
+            .parse()
+            .map_err(|error| format!("invalid port number in {spec}: {error}"))?;
+        let protocol = match protocol.to_lowercase().as_str() {
+            "tcp" => Protocol::Tcp,
+            "udp" => Protocol::Udp,
+            "sctp" => Protocol::Sctp,
+            _ => return Err(format!("unknown protocol in {spec}")),
+        };
+        Ok(ContainerPort { number, protocol })
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PortError {
+    #[error("invalid container port: {0}")]
+    InvalidSpec(String),
+    #[error("the container was not running when its ports were mapped")]
+    NotRunning,
+    #[error("container port {0} is not exposed")]
+    NotExposed(ContainerPort),
+    #[error("container port {0} is exposed but not mapped to a host port")]
+    NotMapped(ContainerPort),
+    #[error("no container port is mapped to a host port")]
+    NoMappedPort,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ports_round_trip_through_their_spec() {
+        for port in [
+            ContainerPort::tcp(5432),
+            ContainerPort::udp(53),
+            ContainerPort::sctp(3868),
+        ] {
+            assert_eq!(port.to_string().parse(), Ok(port));
+        }
+        assert_eq!("8080".parse(), Ok(ContainerPort::tcp(8080)));
+        assert!("8080/quic".parse::<ContainerPort>().is_err());
+        assert!("http".parse::<ContainerPort>().is_err());
+    }
+}
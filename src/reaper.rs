@@ -0,0 +1,97 @@
+use crate::{
+    config::Config, container::GenericContainer, error::TestcontainersError,
+    runtime::ContainerRuntime, session,
+};
+use log::info;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io::{BufRead, BufReader, Write},
+    sync::{Arc, LazyLock},
+};
+use tokio::{net::TcpStream, sync::Mutex};
+
+pub use crate::session::{session_id, SESSION_ID_LABEL};
+
+const RYUK_IMAGE: &str = "testcontainers/ryuk:0.5.1";
+const RYUK_PORT: u16 = 8080;
+
+static REAPER_CONNECTIONS: LazyLock<Mutex<HashMap<Option<String>, std::net::TcpStream>>> =
+    LazyLock::new(Default::default);
+
+pub(crate) fn is_disabled() -> bool {
+    Config::global().ryuk_disabled()
+}
+
+// every docker host gets its own reaper since ryuk only sees the resources of its daemon
+pub(crate) async fn ensure_started(
+    runtime: Arc<dyn ContainerRuntime>,
+) -> Result<(), TestcontainersError> {
+    session::cleanup_orphans_once(runtime.as_ref()).await;
+    if is_disabled() {
+        return Ok(());
+    }
+    let docker_host = runtime.docker_host().map(String::from);
+    let mut connections = REAPER_CONNECTIONS.lock().await;
+    if let Entry::Vacant(entry) = connections.entry(docker_host) {
+        let connection = start(runtime).await?;
+        entry.insert(connection);
+    }
+    Ok(())
+}
+
+async fn start(
+    runtime: Arc<dyn ContainerRuntime>,
+) -> Result<std::net::TcpStream, TestcontainersError> {
+    info!("🐋 Starting resource reaper for session {}", session_id());
+    let socket_volume = format!("{}:/var/run/docker.sock", runtime.daemon_socket());
+    let ryuk = GenericContainer::from_image(RYUK_IMAGE)
+        .with_runtime(runtime)
+        .add_volume(socket_volume)
+        .add_exposed_tcp_port(RYUK_PORT)
+        .wait_for_log_on_startup(".*Started.*")
+        .with_auto_remove(true)
+        .create_without_reaper()
+        .await?;
+    let ryuk = ryuk.start().await?;
+    let host_port = ryuk
+        .get_host_port(format!("{RYUK_PORT}/tcp"))
+        .ok_or_else(|| TestcontainersError::Reaper("port is not mapped".to_string()))?;
+
+    // ryuk removes every resource matching the filters once this connection is closed, which
+    // happens when the test process exits, even if it is killed
+    let stream = TcpStream::connect((ryuk.get_host(), host_port))
+        .await?
+        .into_std()?;
+    // kept outside of any tokio runtime since tests each run their own
+    stream.set_nonblocking(false)?;
+    send_filter(
+        &stream,
+        &format!("label={SESSION_ID_LABEL}={}", session_id()),
+    )?;
+    Ok(stream)
+}
+
+// resources created outside of this crate, like compose stacks, are reaped through extra filters
+pub(crate) async fn register_filter(
+    runtime: Arc<dyn ContainerRuntime>,
+    filter: &str,
+) -> Result<(), TestcontainersError> {
+    ensure_started(runtime.clone()).await?;
+    let connections = REAPER_CONNECTIONS.lock().await;
+    match connections.get(&runtime.docker_host().map(String::from)) {
+        Some(stream) => send_filter(stream, filter),
+        None => Ok(()),
+    }
+}
+
+fn send_filter(mut stream: &std::net::TcpStream, filter: &str) -> Result<(), TestcontainersError> {
+    stream.write_all(format!("{filter}\n").as_bytes())?;
+    let mut acknowledgement = String::new();
+    BufReader::new(stream).read_line(&mut acknowledgement)?;
+    if acknowledgement.trim() != "ACK" {
+        return Err(TestcontainersError::Reaper(format!(
+            "filter {filter} not acknowledged: {acknowledgement}"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,406 @@
+use crate::{
+    docker_client::ContainerClient,
+    error::TestcontainersError,
+    image::{DockerImage, ImageArchive, PullRetry},
+};
+use async_trait::async_trait;
+use docker_api::{
+    opts::{ContainerCreateOpts, NetworkCreateOpts, RegistryAuth, VolumeCreateOpts},
+    Network, Volume,
+};
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use log::warn;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock, Mutex},
+    time::Instant,
+};
+
+type PullKey = (Option<String>, String, Option<String>);
+type ArchiveKey = (Option<String>, ImageArchive);
+type PullFuture = Shared<BoxFuture<'static, Result<(), Arc<docker_api::Error>>>>;
+
+static IN_FLIGHT_PULLS: LazyLock<Mutex<HashMap<PullKey, PullFuture>>> =
+    LazyLock::new(Default::default);
+static LOADED_ARCHIVES: LazyLock<tokio::sync::Mutex<HashSet<ArchiveKey>>> =
+    LazyLock::new(Default::default);
+
+// container settings that docker-api has no option for, any of them requires the raw engine API
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EngineCreateOpts {
+    // docker-api keeps the name of ContainerCreateOpts private, the engine API needs it again
+    pub name: Option<String>,
+    pub platform: Option<String>,
+    pub gpus: Option<Gpus>,
+    pub device_cgroup_rules: Vec<String>,
+    // mount point to mount options, e.g. "rw,size=256m"
+    pub tmpfs: HashMap<String, String>,
+    pub ulimits: Vec<Ulimit>,
+    pub dns: Vec<String>,
+    pub dns_search: Vec<String>,
+    pub dns_options: Vec<String>,
+    pub pid_mode: Option<String>,
+    pub ipc_mode: Option<String>,
+    pub uts_mode: Option<String>,
+    pub init: bool,
+    pub oom_kill_disable: bool,
+    pub oom_score_adj: Option<i32>,
+    pub group_add: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Gpus {
+    All,
+    Count(u32),
+    // indexes or UUIDs as listed by nvidia-smi
+    DeviceIds(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ulimit {
+    pub name: String,
+    pub soft: i64,
+    pub hard: i64,
+}
+
+impl EngineCreateOpts {
+    pub fn is_empty(&self) -> bool {
+        self.settings().is_empty()
+    }
+
+    // the builder methods behind the settings in use, to explain why the engine API is needed
+    pub fn settings(&self) -> Vec<&'static str> {
+        [
+            (self.platform.is_some(), "with_platform"),
+            (self.gpus.is_some(), "with_gpus"),
+            (
+                !self.device_cgroup_rules.is_empty(),
+                "with_device_cgroup_rule",
+            ),
+            (!self.tmpfs.is_empty(), "with_tmpfs"),
+            (!self.ulimits.is_empty(), "with_ulimit"),
+            (!self.dns.is_empty(), "with_dns"),
+            (!self.dns_search.is_empty(), "with_dns_search"),
+            (!self.dns_options.is_empty(), "with_dns_option"),
+            (self.pid_mode.is_some(), "with_pid_mode"),
+            (self.ipc_mode.is_some(), "with_ipc_mode"),
+            (self.uts_mode.is_some(), "with_uts_mode"),
+            (self.init, "with_init"),
+            (self.oom_kill_disable, "with_oom_kill_disable"),
+            (self.oom_score_adj.is_some(), "with_oom_score_adj"),
+            (!self.group_add.is_empty(), "with_group_add"),
+        ]
+        .into_iter()
+        .filter_map(|(used, setting)| used.then_some(setting))
+        .collect()
+    }
+}
+
+// backends speak the docker engine API types, the default one is DockerClient
+#[async_trait]
+pub trait ContainerRuntime: Send + Sync {
+    fn docker_host(&self) -> Option<&str>;
+
+    // the daemon socket as the daemon sees it, mounted into the reaper
+    fn daemon_socket(&self) -> &str;
+
+    async fn image_exists_locally(&self, image: &DockerImage) -> Result<bool, TestcontainersError>;
+
+    async fn image_platform(
+        &self,
+        image: &DockerImage,
+    ) -> Result<Option<String>, TestcontainersError>;
+
+    async fn pull(
+        &self,
+        image: &DockerImage,
+        platform: Option<&str>,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<(), docker_api::Error>;
+
+    // auths are keyed by the address of the registries the build pulls base images from
+    async fn build(
+        &self,
+        image: &DockerImage,
+        platform: Option<&str>,
+        auths: &HashMap<String, RegistryAuth>,
+    ) -> Result<(), docker_api::Error>;
+
+    async fn load(&self, archive: &ImageArchive) -> Result<(), docker_api::Error>;
+
+    async fn create(
+        &self,
+        opts: &ContainerCreateOpts,
+        engine_opts: &EngineCreateOpts,
+    ) -> Result<ContainerClient, docker_api::Error>;
+
+    async fn create_network(
+        &self,
+        opts: &NetworkCreateOpts,
+    ) -> Result<Network, TestcontainersError>;
+
+    async fn create_volume(&self, opts: &VolumeCreateOpts) -> Result<Volume, TestcontainersError>;
+
+    async fn find_running(
+        &self,
+        label: &str,
+        value: &str,
+    ) -> Result<Option<ContainerClient>, TestcontainersError>;
+
+    // the labels of every container, network and volume carrying the given label
+    async fn list_labeled(
+        &self,
+        label: &str,
+    ) -> Result<Vec<HashMap<String, String>>, TestcontainersError>;
+
+    async fn remove_labeled(&self, label: &str, value: &str) -> Result<(), TestcontainersError>;
+}
+
+// concurrent pulls of the same image and platform on the same docker host share a single pull
+pub(crate) async fn pull_once(
+    runtime: Arc<dyn ContainerRuntime>,
+    image: &DockerImage,
+    platform: Option<String>,
+    auth: Option<RegistryAuth>,
+    retry: PullRetry,
+) -> Result<(), docker_api::Error> {
+    let key = (
+        runtime.docker_host().map(String::from),
+        image.get_full_name(),
+        platform.clone(),
+    );
+    let pull = {
+        let mut pulls = IN_FLIGHT_PULLS.lock().unwrap();
+        pulls
+            .entry(key.clone())
+            .or_insert_with(|| {
+                let image = image.clone();
+                async move {
+                    let result = pull_with_retry(
+                        runtime.as_ref(),
+                        &image,
+                        platform.as_deref(),
+                        auth.as_ref(),
+                        retry,
+                    )
+                    .await
+                    .map_err(Arc::new);
+                    IN_FLIGHT_PULLS.lock().unwrap().remove(&key);
+                    result
+                }
+                .boxed()
+                .shared()
+            })
+            .clone()
+    };
+    pull.await.map_err(|error| {
+        Arc::try_unwrap(error)
+            .unwrap_or_else(|error| docker_api::Error::StringError(error.to_string()))
+    })
+}
+
+// an archive is loaded once per docker host, later containers find its images locally
+pub(crate) async fn load_once(
+    runtime: &dyn ContainerRuntime,
+    archive: &ImageArchive,
+) -> Result<(), docker_api::Error> {
+    let mut loaded = LOADED_ARCHIVES.lock().await;
+    let key = (runtime.docker_host().map(String::from), archive.clone());
+    if !loaded.contains(&key) {
+        runtime.load(archive).await?;
+        loaded.insert(key);
+    }
+    Ok(())
+}
+
+async fn pull_with_retry(
+    runtime: &dyn ContainerRuntime,
+    image: &DockerImage,
+    platform: Option<&str>,
+    auth: Option<&RegistryAuth>,
+    retry: PullRetry,
+) -> Result<(), docker_api::Error> {
+    let started = Instant::now();
+    let mut attempt = 1;
+    loop {
+        match runtime.pull(image, platform, auth).await {
+            Ok(()) => return Ok(()),
+            Err(error)
+                if is_transient(&error)
+                    && attempt < retry.max_attempts()
+                    && started.elapsed() + retry.backoff(attempt) < retry.max_duration() =>
+            {
+                warn!("🐋 Pulling image {image} failed, retrying: {error}");
+                tokio::time::sleep(retry.backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn is_transient(error: &docker_api::Error) -> bool {
+    match error {
+        docker_api::Error::Fault { code, .. } => {
+            code.is_server_error() || [408, 429].contains(&code.as_u16())
+        }
+        docker_api::Error::Hyper(_) | docker_api::Error::IO(_) => true,
+        docker_api::Error::Error(error) => matches!(
+            error,
+            docker_api::conn::Error::Hyper(_) | docker_api::conn::Error::IO(_)
+        ),
+        docker_api::Error::StringError(message) => {
+            let message = message.to_lowercase();
+            [
+                "timeout",
+                "status: 5",
+                "toomanyrequests",
+                "connection reset",
+                "eof",
+            ]
+            .iter()
+            .any(|transient| message.contains(transient))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::future::join_all;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    #[derive(Default)]
+    struct SlowPullRuntime {
+        pulls: AtomicUsize,
+        failures: usize,
+    }
+
+    #[async_trait]
+    impl ContainerRuntime for SlowPullRuntime {
+        fn docker_host(&self) -> Option<&str> {
+            Some("tcp://slow-pull:2375")
+        }
+
+        fn daemon_socket(&self) -> &str {
+            "/var/run/docker.sock"
+        }
+
+        async fn image_exists_locally(&self, _: &DockerImage) -> Result<bool, TestcontainersError> {
+            Ok(false)
+        }
+
+        async fn image_platform(
+            &self,
+            _: &DockerImage,
+        ) -> Result<Option<String>, TestcontainersError> {
+            unimplemented!()
+        }
+
+        async fn pull(
+            &self,
+            _: &DockerImage,
+            _: Option<&str>,
+            _: Option<&RegistryAuth>,
+        ) -> Result<(), docker_api::Error> {
+            let pulls = self.pulls.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if pulls < self.failures {
+                return Err(docker_api::Error::StringError(
+                    "received unexpected HTTP status: 503 Service Unavailable".to_string(),
+                ));
+            }
+            Ok(())
+        }
+
+        async fn build(
+            &self,
+            _: &DockerImage,
+            _: Option<&str>,
+            _: &HashMap<String, RegistryAuth>,
+        ) -> Result<(), docker_api::Error> {
+            unimplemented!()
+        }
+
+        async fn load(&self, _: &ImageArchive) -> Result<(), docker_api::Error> {
+            unimplemented!()
+        }
+
+        async fn create(
+            &self,
+            _: &ContainerCreateOpts,
+            _: &EngineCreateOpts,
+        ) -> Result<ContainerClient, docker_api::Error> {
+            unimplemented!()
+        }
+
+        async fn create_network(
+            &self,
+            _: &NetworkCreateOpts,
+        ) -> Result<Network, TestcontainersError> {
+            unimplemented!()
+        }
+
+        async fn create_volume(&self, _: &VolumeCreateOpts) -> Result<Volume, TestcontainersError> {
+            unimplemented!()
+        }
+
+        async fn find_running(
+            &self,
+            _: &str,
+            _: &str,
+        ) -> Result<Option<ContainerClient>, TestcontainersError> {
+            unimplemented!()
+        }
+
+        async fn list_labeled(
+            &self,
+            _: &str,
+        ) -> Result<Vec<HashMap<String, String>>, TestcontainersError> {
+            unimplemented!()
+        }
+
+        async fn remove_labeled(&self, _: &str, _: &str) -> Result<(), TestcontainersError> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_pulls_of_an_image_are_deduplicated() {
+        let runtime = Arc::new(SlowPullRuntime::default());
+        let image: DockerImage = "alpine:latest".try_into().unwrap();
+        let pulls =
+            (0..5).map(|_| pull_once(runtime.clone(), &image, None, None, PullRetry::default()));
+        for result in join_all(pulls).await {
+            result.unwrap();
+        }
+        assert_eq!(runtime.pulls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn transient_pull_failures_are_retried() {
+        let runtime = SlowPullRuntime {
+            failures: 2,
+            ..Default::default()
+        };
+        let image: DockerImage = "alpine:3.18".try_into().unwrap();
+        let retry = PullRetry::default().with_initial_backoff(Duration::from_millis(1));
+        pull_with_retry(&runtime, &image, None, None, retry)
+            .await
+            .unwrap();
+        assert_eq!(runtime.pulls.load(Ordering::Relaxed), 3);
+
+        let runtime = SlowPullRuntime {
+            failures: 3,
+            ..Default::default()
+        };
+        assert!(pull_with_retry(&runtime, &image, None, None, retry)
+            .await
+            .is_err());
+        assert_eq!(runtime.pulls.load(Ordering::Relaxed), 3);
+    }
+}
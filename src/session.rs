@@ -0,0 +1,165 @@
+use crate::{docker_client::DockerClient, error::TestcontainersError, runtime::ContainerRuntime};
+use log::{info, warn};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    path::Path,
+    process::{Command, Stdio},
+    sync::{LazyLock, OnceLock},
+    time::SystemTime,
+};
+use tokio::sync::Mutex;
+
+pub const SESSION_ID_LABEL: &str = "org.testcontainers.session-id";
+pub const SESSION_PID_LABEL: &str = "org.testcontainers.session-pid";
+pub const SESSION_HOST_LABEL: &str = "org.testcontainers.session-host";
+
+static CURRENT: OnceLock<Session> = OnceLock::new();
+static CLEANED_DOCKER_HOSTS: LazyLock<Mutex<HashSet<Option<String>>>> =
+    LazyLock::new(Default::default);
+
+// resources of a session carry its labels, which is what the reaper filters on; reusable
+// containers are left out of the session so that they outlive it
+#[derive(Debug)]
+pub struct Session {
+    id: String,
+    pid: u32,
+    host: String,
+}
+
+impl Session {
+    pub fn current() -> &'static Session {
+        CURRENT.get_or_init(|| {
+            let pid = std::process::id();
+            let mut hasher = DefaultHasher::new();
+            pid.hash(&mut hasher);
+            SystemTime::now().hash(&mut hasher);
+            Session {
+                id: format!("{:016x}", hasher.finish()),
+                pid,
+                host: hostname(),
+            }
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn labels(&self) -> HashMap<String, String> {
+        HashMap::from([
+            (SESSION_ID_LABEL.to_string(), self.id.clone()),
+            (SESSION_PID_LABEL.to_string(), self.pid.to_string()),
+            (SESSION_HOST_LABEL.to_string(), self.host.clone()),
+        ])
+    }
+
+    pub async fn cleanup_orphans(&self) -> Result<Vec<String>, TestcontainersError> {
+        self.cleanup_orphans_with(DockerClient::shared().as_ref())
+            .await
+    }
+
+    // only sessions started on this host by a process that is gone are considered orphaned,
+    // the resources of concurrent test processes are left alone
+    pub async fn cleanup_orphans_with(
+        &self,
+        runtime: &dyn ContainerRuntime,
+    ) -> Result<Vec<String>, TestcontainersError> {
+        let mut orphans: Vec<_> = runtime
+            .list_labeled(SESSION_ID_LABEL)
+            .await?
+            .iter()
+            .filter(|labels| self.is_orphan(labels, is_process_running))
+            .filter_map(|labels| labels.get(SESSION_ID_LABEL).cloned())
+            .collect();
+        orphans.sort();
+        orphans.dedup();
+        for session_id in &orphans {
+            info!("🐋 Removing leftovers of session {session_id}");
+            runtime.remove_labeled(SESSION_ID_LABEL, session_id).await?;
+        }
+        Ok(orphans)
+    }
+
+    fn is_orphan<F: Fn(u32) -> bool>(
+        &self,
+        labels: &HashMap<String, String>,
+        is_running: F,
+    ) -> bool {
+        let from_this_host = labels.get(SESSION_HOST_LABEL) == Some(&self.host);
+        let other_session = labels.get(SESSION_ID_LABEL) != Some(&self.id);
+        let pid = labels
+            .get(SESSION_PID_LABEL)
+            .and_then(|pid| pid.parse().ok());
+        from_this_host && other_session && pid.is_some_and(|pid| !is_running(pid))
+    }
+}
+
+pub fn session_id() -> &'static str {
+    Session::current().id()
+}
+
+// failing to clean up previous runs must not prevent this one from running
+pub(crate) async fn cleanup_orphans_once(runtime: &dyn ContainerRuntime) {
+    let mut cleaned = CLEANED_DOCKER_HOSTS.lock().await;
+    if cleaned.insert(runtime.docker_host().map(String::from)) {
+        if let Err(error) = Session::current().cleanup_orphans_with(runtime).await {
+            warn!("🐋 Unable to remove the leftovers of previous sessions: {error}");
+        }
+    }
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn is_process_running(pid: u32) -> bool {
+    if Path::new("/proc/self").exists() {
+        return Path::new(&format!("/proc/{pid}")).exists();
+    }
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(session: &str, pid: &str, host: &str) -> HashMap<String, String> {
+        HashMap::from([
+            (SESSION_ID_LABEL.to_string(), session.to_string()),
+            (SESSION_PID_LABEL.to_string(), pid.to_string()),
+            (SESSION_HOST_LABEL.to_string(), host.to_string()),
+        ])
+    }
+
+    #[test]
+    fn only_dead_sessions_of_this_host_are_orphaned() {
+        let session = Session {
+            id: "current".to_string(),
+            pid: 1000,
+            host: "ci-runner".to_string(),
+        };
+        let is_running = |pid| pid == 1000 || pid == 2000;
+        assert!(session.is_orphan(&labels("crashed", "3000", "ci-runner"), is_running));
+        assert!(!session.is_orphan(&labels("concurrent", "2000", "ci-runner"), is_running));
+        assert!(!session.is_orphan(&labels("remote", "3000", "laptop"), is_running));
+        assert!(!session.is_orphan(&labels("current", "3000", "ci-runner"), is_running));
+        assert!(!session.is_orphan(
+            &HashMap::from([(SESSION_ID_LABEL.to_string(), "legacy".to_string())]),
+            is_running
+        ));
+    }
+
+    #[test]
+    fn current_process_is_running() {
+        assert!(is_process_running(std::process::id()));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    container::{GenericContainerBuilder, RunningContainer},
+    error::TestcontainersError,
+};
+use std::future::Future;
+use tokio::sync::OnceCell;
+
+// a container started once and used by every test of the process, typically held in a static:
+//
+//     static POSTGRES: SharedContainer<PostgresContainer> = SharedContainer::new();
+//     let postgres = POSTGRES.get_or_init(postgresql()).await?;
+//
+// it is never stopped explicitly: like any other container of the session it is removed by the
+// reaper once the test process exits
+pub struct SharedContainer<C = RunningContainer> {
+    container: OnceCell<C>,
+}
+
+impl<C> SharedContainer<C> {
+    pub const fn new() -> Self {
+        SharedContainer {
+            container: OnceCell::const_new(),
+        }
+    }
+
+    pub fn get(&self) -> Option<&C> {
+        self.container.get()
+    }
+}
+
+impl<C> Default for SharedContainer<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: From<RunningContainer>> SharedContainer<C> {
+    // concurrent callers wait for the first one to start the container; the builder is only
+    // awaited by the caller that starts it, and a failed start is retried by the next caller
+    pub async fn get_or_init<F>(&self, builder: F) -> Result<&C, TestcontainersError>
+    where
+        F: Future<Output = GenericContainerBuilder>,
+    {
+        self.container
+            .get_or_try_init(|| async {
+                let container = builder.await.create().await?.start().await?;
+                Ok(C::from(container))
+            })
+            .await
+    }
+}
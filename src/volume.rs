@@ -0,0 +1,69 @@
+use crate::{
+    docker_client::DockerClient, error::TestcontainersError, reaper, runtime::ContainerRuntime,
+    session::Session,
+};
+use docker_api::{models, opts::VolumeCreateOpts};
+use std::{collections::HashMap, sync::Arc};
+
+pub struct Volume {
+    name: String,
+    inner_volume: docker_api::Volume,
+}
+
+impl Volume {
+    pub async fn create<S: Into<String>>(name: S) -> Result<Volume, TestcontainersError> {
+        Self::create_on(DockerClient::shared(), name).await
+    }
+
+    // for containers created with_docker_host or with_runtime, which only see the volumes of
+    // their own daemon
+    pub async fn create_on<S: Into<String>>(
+        runtime: Arc<dyn ContainerRuntime>,
+        name: S,
+    ) -> Result<Volume, TestcontainersError> {
+        Self::create_with_driver_on(runtime, name, "local", HashMap::new()).await
+    }
+
+    pub async fn create_with_driver<S: Into<String>>(
+        name: S,
+        driver: &str,
+        driver_opts: HashMap<String, String>,
+    ) -> Result<Volume, TestcontainersError> {
+        Self::create_with_driver_on(DockerClient::shared(), name, driver, driver_opts).await
+    }
+
+    // driver options are driver specific, e.g. type, device and o for the local driver
+    pub async fn create_with_driver_on<S: Into<String>>(
+        runtime: Arc<dyn ContainerRuntime>,
+        name: S,
+        driver: &str,
+        driver_opts: HashMap<String, String>,
+    ) -> Result<Volume, TestcontainersError> {
+        let name: String = name.into();
+        reaper::ensure_started(runtime.clone()).await?;
+        let opts = VolumeCreateOpts::builder()
+            .name(&name)
+            .driver(driver)
+            .driver_opts(driver_opts)
+            .labels(Session::current().labels())
+            .build();
+        let inner_volume = runtime.create_volume(&opts).await?;
+        println!("🐋 Volume {name} created");
+        Ok(Volume { name, inner_volume })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn inspect(&self) -> Result<models::Volume, TestcontainersError> {
+        Ok(self.inner_volume.inspect().await?)
+    }
+
+    // containers using the volume have to be removed first
+    pub async fn remove(self) -> Result<(), TestcontainersError> {
+        self.inner_volume.delete().await?;
+        println!("🐋 Volume {} removed", self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    docker_client::{ContainerClient, ExecResult, LogOptions},
+    error::TestcontainersError,
+    port::ContainerPort,
+};
+use futures_util::future::{BoxFuture, FutureExt};
+use regex::Regex;
+use std::{
+    fmt::Display,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{io::AsyncReadExt, net::TcpStream};
+
+pub use async_trait::async_trait;
+
+#[async_trait]
+pub trait WaitStrategy: Send + Sync {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError>;
+
+    fn describe(&self) -> String {
+        "custom wait strategy".to_string()
+    }
+}
+
+pub struct ContainerView<'a> {
+    client: &'a ContainerClient,
+    started: Instant,
+}
+
+impl<'a> ContainerView<'a> {
+    pub(crate) fn new(client: &'a ContainerClient) -> Self {
+        ContainerView {
+            client,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn host(&self) -> String {
+        self.client.host()
+    }
+
+    pub async fn logs(&self) -> Result<String, TestcontainersError> {
+        self.client.logs().await
+    }
+
+    pub async fn logs_with(&self, options: &LogOptions) -> Result<String, TestcontainersError> {
+        self.client.logs_with(options).await
+    }
+
+    pub async fn health_state(&self) -> Result<Option<String>, TestcontainersError> {
+        self.client.health_state().await
+    }
+
+    pub async fn exec(&self, command_parts: &[&str]) -> Result<ExecResult, TestcontainersError> {
+        let command: Vec<String> = command_parts.iter().map(|s| s.to_string()).collect();
+        self.client.exec(&command).await
+    }
+
+    pub async fn get_host_port<S: Into<String>>(
+        &self,
+        container_port_spec: S,
+    ) -> Result<Option<u16>, TestcontainersError> {
+        let container_port_spec = container_port_spec.into();
+        let running_state = self.client.inspect().await?;
+        if running_state.host_network {
+            return Ok(container_port_spec
+                .parse::<ContainerPort>()
+                .ok()
+                .map(|port| port.number));
+        }
+        Ok(running_state.ports.get(&container_port_spec).copied())
+    }
+}
+
+#[derive(Clone)]
+pub enum ReadyStrategy {
+    ExecCommand(Vec<String>),
+    FileExists(String),
+    LogMessageRegExp(Regex, LogOptions),
+    PortListening(String),
+    StateHealthy,
+    Exited,
+    All(Vec<ReadyStrategy>),
+    Any(Vec<ReadyStrategy>),
+    Custom(Arc<dyn WaitStrategy>),
+    WithTimeout(Box<ReadyStrategy>, Duration),
+    None,
+}
+
+impl Display for ReadyStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadyStrategy::ExecCommand(command) => write!(f, "command `{}`", command.join(" ")),
+            ReadyStrategy::FileExists(path) => write!(f, "file {path}"),
+            ReadyStrategy::LogMessageRegExp(regex, _) => write!(f, "log matching /{regex}/"),
+            ReadyStrategy::PortListening(port) => write!(f, "port {port} listening"),
+            ReadyStrategy::StateHealthy => write!(f, "healthy state"),
+            ReadyStrategy::Exited => write!(f, "container exit"),
+            ReadyStrategy::All(strategies) => Self::fmt_list(f, "all of", strategies),
+            ReadyStrategy::Any(strategies) => Self::fmt_list(f, "any of", strategies),
+            ReadyStrategy::Custom(strategy) => write!(f, "{}", strategy.describe()),
+            ReadyStrategy::WithTimeout(strategy, timeout) => {
+                write!(f, "{strategy} within {timeout:?}")
+            }
+            ReadyStrategy::None => write!(f, "nothing"),
+        }
+    }
+}
+
+impl ReadyStrategy {
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        ReadyStrategy::WithTimeout(Box::new(self), timeout)
+    }
+
+    fn fmt_list(
+        f: &mut std::fmt::Formatter<'_>,
+        prefix: &str,
+        strategies: &[ReadyStrategy],
+    ) -> std::fmt::Result {
+        let descriptions: Vec<String> = strategies.iter().map(|s| s.to_string()).collect();
+        write!(f, "{prefix} [{}]", descriptions.join(", "))
+    }
+
+    pub(crate) async fn wait(
+        &self,
+        container: &ContainerClient,
+        timeout: Duration,
+        consecutive_successes: u32,
+    ) -> Result<(), TestcontainersError> {
+        let view = ContainerView::new(container);
+        let timeout_instant = Instant::now() + timeout;
+        let mut successes = 0;
+        loop {
+            if self.check(&view).await? {
+                successes += 1;
+                if successes >= consecutive_successes {
+                    return Ok(());
+                }
+            } else {
+                successes = 0;
+                if container.has_exited().await? {
+                    return Err(TestcontainersError::StartupExited {
+                        container_id: container.id().to_string(),
+                        strategy: self.to_string(),
+                        elapsed: view.started.elapsed(),
+                        logs: String::new(),
+                    });
+                }
+            }
+            if timeout_instant < Instant::now() {
+                break;
+            } else {
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            }
+        }
+        Err(TestcontainersError::StartupTimeout {
+            container_id: container.id().to_string(),
+            strategy: self.to_string(),
+            elapsed: view.started.elapsed(),
+            logs: String::new(),
+        })
+    }
+
+    fn check<'a>(
+        &'a self,
+        container: &'a ContainerView,
+    ) -> BoxFuture<'a, Result<bool, TestcontainersError>> {
+        async move {
+            match self {
+                ReadyStrategy::ExecCommand(command) => {
+                    Ok(container.client.exec(command).await?.exit_code == 0)
+                }
+                ReadyStrategy::FileExists(path) => {
+                    Ok(container.exec(&["test", "-e", path]).await?.exit_code == 0)
+                }
+                ReadyStrategy::LogMessageRegExp(regex, options) => {
+                    let logs = container.client.logs_with(options).await?;
+                    Ok(regex.is_match(&logs))
+                }
+                ReadyStrategy::PortListening(container_port_spec) => {
+                    match container.get_host_port(container_port_spec).await? {
+                        Some(host_port) => {
+                            Ok(Self::accepts_connections(&container.host(), host_port).await)
+                        }
+                        None => Ok(false),
+                    }
+                }
+                ReadyStrategy::StateHealthy => match container.health_state().await?.as_deref() {
+                    Some("healthy") => Ok(true),
+                    Some("unhealthy") => Err(TestcontainersError::Unhealthy {
+                        container_id: container.client.id().to_string(),
+                        logs: String::new(),
+                    }),
+                    _ => Ok(false),
+                },
+                ReadyStrategy::Exited => container.client.has_exited().await,
+                ReadyStrategy::All(strategies) => {
+                    for strategy in strategies {
+                        if !strategy.check(container).await? {
+                            return Ok(false);
+                        }
+                    }
+                    Ok(true)
+                }
+                // a child past its own timeout is only not ready, the others may still succeed
+                ReadyStrategy::Any(strategies) => {
+                    let mut expired = vec![];
+                    for strategy in strategies {
+                        match strategy.check(container).await {
+                            Ok(true) => return Ok(true),
+                            Ok(false) => {}
+                            Err(error @ TestcontainersError::StartupTimeout { .. }) => {
+                                expired.push(error)
+                            }
+                            Err(error) => return Err(error),
+                        }
+                    }
+                    match expired.len() == strategies.len() {
+                        true => expired.pop().map_or(Ok(false), Err),
+                        false => Ok(false),
+                    }
+                }
+                ReadyStrategy::Custom(strategy) => strategy.is_ready(container).await,
+                ReadyStrategy::WithTimeout(strategy, timeout) => {
+                    if strategy.check(container).await? {
+                        Ok(true)
+                    } else if container.started.elapsed() > *timeout {
+                        Err(TestcontainersError::StartupTimeout {
+                            container_id: container.client.id().to_string(),
+                            strategy: strategy.to_string(),
+                            elapsed: container.started.elapsed(),
+                            logs: String::new(),
+                        })
+                    } else {
+                        Ok(false)
+                    }
+                }
+                ReadyStrategy::None => Ok(true),
+            }
+        }
+        .boxed()
+    }
+
+    async fn accepts_connections(host: &str, host_port: u16) -> bool {
+        let Ok(mut stream) = TcpStream::connect((host, host_port)).await else {
+            return false;
+        };
+        // the docker userland proxy accepts connections on behalf of the container even if
+        // nothing listens yet, it closes them right away in that case
+        let mut buffer = [0; 1];
+        match tokio::time::timeout(Duration::from_millis(100), stream.read(&mut buffer)).await {
+            Ok(Ok(0)) | Ok(Err(_)) => false,
+            Ok(Ok(_)) | Err(_) => true,
+        }
+    }
+}
+
+#[async_trait]
+impl WaitStrategy for ReadyStrategy {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        self.check(container).await
+    }
+}
+
+#[cfg(feature = "tokio-postgres")]
+#[derive(Clone)]
+pub struct PostgresConnection {
+    container_port_spec: String,
+    user: String,
+    password: String,
+    database: String,
+    validation_query: Option<String>,
+}
+
+#[cfg(feature = "tokio-postgres")]
+impl PostgresConnection {
+    pub fn new<S: Into<String>>(user: S, password: S, database: S) -> Self {
+        PostgresConnection {
+            container_port_spec: "5432/tcp".to_string(),
+            user: user.into(),
+            password: password.into(),
+            database: database.into(),
+            validation_query: None,
+        }
+    }
+
+    pub fn on_port<S: Into<String>>(mut self, container_port_spec: S) -> Self {
+        self.container_port_spec = container_port_spec.into();
+        self
+    }
+
+    pub fn with_validation_query<S: Into<String>>(mut self, query: S) -> Self {
+        self.validation_query = Some(query.into());
+        self
+    }
+}
+
+#[cfg(feature = "tokio-postgres")]
+#[async_trait]
+impl WaitStrategy for PostgresConnection {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, TestcontainersError> {
+        let Some(host_port) = container.get_host_port(&self.container_port_spec).await? else {
+            return Ok(false);
+        };
+        let connection = tokio_postgres::Config::new()
+            .host(container.host())
+            .port(host_port)
+            .user(&self.user)
+            .password(&self.password)
+            .dbname(&self.database)
+            .connect_timeout(Duration::from_secs(1))
+            .connect(tokio_postgres::NoTls)
+            .await;
+        let Ok((client, connection)) = connection else {
+            return Ok(false);
+        };
+        tokio::spawn(connection);
+        match &self.validation_query {
+            Some(query) => Ok(client.simple_query(query).await.is_ok()),
+            None => Ok(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use docker_api::Docker;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct ReadyAfter {
+        checks: AtomicU32,
+        ready_after: u32,
+    }
+
+    #[async_trait]
+    impl WaitStrategy for ReadyAfter {
+        async fn is_ready(&self, _: &ContainerView) -> Result<bool, TestcontainersError> {
+            Ok(self.checks.fetch_add(1, Ordering::Relaxed) + 1 >= self.ready_after)
+        }
+    }
+
+    fn ready_after(ready_after: u32) -> ReadyStrategy {
+        ReadyStrategy::Custom(Arc::new(ReadyAfter {
+            checks: AtomicU32::new(0),
+            ready_after,
+        }))
+    }
+
+    #[tokio::test]
+    async fn any_strategy_outlives_expired_children() {
+        let docker = Docker::unix("/nonexistent.sock");
+        let client = ContainerClient::new(docker.clone(), None, docker.containers().get("id"));
+        let view = ContainerView::new(&client);
+        let never = ready_after(u32::MAX).with_timeout(Duration::ZERO);
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        let strategy = ReadyStrategy::Any(vec![never.clone(), ready_after(3)]);
+        assert!(!strategy.check(&view).await.unwrap());
+        assert!(!strategy.check(&view).await.unwrap());
+        assert!(strategy.check(&view).await.unwrap());
+
+        let strategy = ReadyStrategy::Any(vec![never.clone(), never]);
+        assert!(matches!(
+            strategy.check(&view).await,
+            Err(TestcontainersError::StartupTimeout { .. })
+        ));
+    }
+
+    #[test]
+    fn can_describe_strategies() {
+        let strategy = ReadyStrategy::All(vec![
+            ReadyStrategy::PortListening("5432/tcp".into()),
+            ReadyStrategy::LogMessageRegExp("ready".parse().unwrap(), LogOptions::default())
+                .with_timeout(Duration::from_secs(5)),
+        ]);
+        assert_eq!(
+            strategy.to_string(),
+            "all of [port 5432/tcp listening, log matching /ready/ within 5s]"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::Parser, parse_macro_input, punctuated::Punctuated, Error, FnArg, ItemFn, MetaNameValue,
+    Pat, ReturnType, Token,
+};
+
+// #[testcontainers::test(db = postgresql())] async fn test(db: PostgresContainer) { ... }
+//
+// every argument is awaited to get the builder of the container handed to the parameter of the
+// same name, through From<RunningContainer>; the containers are removed once the test is over,
+// whether it passed, failed or panicked
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let declarations = match Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse(args) {
+        Ok(declarations) => declarations,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let function = parse_macro_input!(item as ItemFn);
+    match expand(declarations, function) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(
+    declarations: Punctuated<MetaNameValue, Token![,]>,
+    function: ItemFn,
+) -> Result<proc_macro2::TokenStream, Error> {
+    if function.sig.asyncness.is_none() {
+        return Err(Error::new_spanned(
+            function.sig.fn_token,
+            "#[testcontainers::test] requires an async function",
+        ));
+    }
+    let mut starts = vec![];
+    let mut bindings = vec![];
+    for input in &function.sig.inputs {
+        let FnArg::Typed(parameter) = input else {
+            return Err(Error::new_spanned(input, "tests can't take self"));
+        };
+        let Pat::Ident(pattern) = parameter.pat.as_ref() else {
+            return Err(Error::new_spanned(
+                &parameter.pat,
+                "container parameters must be plain identifiers",
+            ));
+        };
+        let declaration = declarations
+            .iter()
+            .find(|declaration| declaration.path.is_ident(&pattern.ident))
+            .ok_or_else(|| {
+                Error::new_spanned(
+                    &pattern.ident,
+                    format!("no container declared for parameter {}", pattern.ident),
+                )
+            })?;
+        let running = syn::Ident::new(
+            &format!("__testcontainers_{}", pattern.ident),
+            Span::mixed_site(),
+        );
+        let builder = &declaration.value;
+        let ty = &parameter.ty;
+        starts.push(quote! {
+            let #running = __testcontainers_cleanup.start((#builder).await).await;
+        });
+        bindings.push(quote! {
+            let #pattern: #ty = ::std::convert::From::from(#running);
+        });
+    }
+    for declaration in &declarations {
+        let declared = function.sig.inputs.iter().any(|input| {
+            matches!(input, FnArg::Typed(parameter)
+                if matches!(parameter.pat.as_ref(), Pat::Ident(pattern) if declaration.path.is_ident(&pattern.ident)))
+        });
+        if !declared {
+            return Err(Error::new_spanned(
+                &declaration.path,
+                "no parameter matches this container",
+            ));
+        }
+    }
+
+    let attributes = &function.attrs;
+    let visibility = &function.vis;
+    let name = &function.sig.ident;
+    let body = &function.block;
+    let output = match &function.sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    Ok(quote! {
+        #[::tokio::test]
+        #(#attributes)*
+        #visibility async fn #name() -> #output {
+            let mut __testcontainers_cleanup =
+                ::testcontainers::macro_support::ContainerCleanup::default();
+            #(#starts)*
+            let __testcontainers_result = ::testcontainers::macro_support::catch_unwind(async move {
+                #(#bindings)*
+                let __testcontainers_output: #output = #body;
+                __testcontainers_output
+            })
+            .await;
+            __testcontainers_cleanup.remove_all().await;
+            ::testcontainers::macro_support::resume(__testcontainers_result)
+        }
+    })
+}
@@ -1,23 +1,1166 @@
-use docker_api::Error;
-use testcontainers::postgresql;
+use docker_api::{
+    opts::{ContainerCreateOpts, NetworkCreateOpts, RegistryAuth, TagOpts, VolumeCreateOpts},
+    Network as DockerNetwork, Volume as DockerVolume,
+};
+use futures_util::TryStreamExt;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use testcontainers::{
+    activemq_artemis, blocking,
+    compose::DockerCompose,
+    consul,
+    container::{GenericContainer, GenericContainerBuilder, ReadyStrategy},
+    docker_client::{ContainerClient, DockerClient, ExecOptions, LogOptions},
+    elasticsearch_secured,
+    error::TestcontainersError as Error,
+    etcd,
+    group::ContainerGroup,
+    image::{DockerImage, ImageArchive, PullPolicy},
+    k3s, kafka, keycloak_with_realm_import, localstack_with_services, mailpit, minio,
+    modules::{
+        artemis::ArtemisContainer,
+        consul::ConsulContainer,
+        elasticsearch::ElasticsearchContainer,
+        etcd::EtcdContainer,
+        k3s::K3sContainer,
+        kafka::KafkaContainer,
+        keycloak::KeycloakContainer,
+        localstack::{LocalStackContainer, LocalStackService},
+        mailpit::MailpitContainer,
+        minio::{Minio, MinioContainer},
+        mongodb::MongoDbContainer,
+        mysql::MySqlContainer,
+        nats::NatsContainer,
+        nginx::NginxContainer,
+        ollama::OllamaContainer,
+        openldap::OpenLdapContainer,
+        postgresql::{Postgres, PostgresContainer},
+        pulsar::PulsarContainer,
+        rabbitmq::RabbitMqContainer,
+        redis::{Redis, RedisContainer},
+        scylla::ScyllaContainer,
+        sftp::SftpContainer,
+        toxiproxy::{Toxic, ToxicDirection, ToxiproxyContainer},
+        trino::TrinoContainer,
+        wiremock::WireMockContainer,
+        ModuleBuilder,
+    },
+    mongodb_replica_set,
+    mount::BindMode,
+    mysql, nats_with_jetstream,
+    network::Network,
+    nginx_with_static_content, ollama, openldap_with_ldif, pgvector,
+    port::{ContainerPort, PortError, Protocol},
+    postgresql, pulsar, rabbitmq, redis,
+    runtime::{ContainerRuntime, EngineCreateOpts},
+    scylla,
+    session::{Session, SESSION_ID_LABEL, SESSION_PID_LABEL},
+    sftp_server,
+    shared::SharedContainer,
+    timescaledb, toxiproxy, trino_with_catalogs,
+    volume::Volume,
+    wait::{async_trait, ContainerView, WaitStrategy},
+    wiremock,
+};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 use tokio_postgres::NoTls;
 
 #[tokio::test]
 async fn should_create_postgresql_container() -> Result<(), Error> {
     let container = postgresql().await.create().await?;
-    container.start().await?;
-    container.stop().await?;
+    let container = container.start().await?;
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_expose_container_identity() -> Result<(), Error> {
+    let container = GenericContainer::from_image("redis:7").create().await?;
+    let name = container.name().await?;
+    assert!(!name.starts_with('/'));
+    let container = container.start().await?;
+    assert_eq!(container.name(), name);
+    assert_eq!(container.image().to_string(), "redis:7");
+    let inspect = container.inspect().await?;
+    assert_eq!(inspect.id.as_deref(), Some(container.id()));
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_name_containers() -> Result<(), Error> {
+    let name = format!("testcontainers-named-{}", Session::current().id());
+    let start = || {
+        GenericContainer::from_image("redis:7")
+            .with_container_name(&name)
+            .with_hostname("cache")
+            .with_domainname("example.test")
+            .create()
+    };
+    let first = start().await?.start().await?;
+    assert_eq!(first.name(), name);
+    let result = first.exec(&["hostname", "-f"]).await?;
+    assert_eq!(result.stdout.trim(), "cache.example.test");
+    let second = start().await?.start().await?;
+    assert!(second.name().starts_with(&format!("{name}-")));
+    first.stop_and_remove().await?;
+    second.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_run_as_user_in_workdir() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&[
+            "sh",
+            "-c",
+            "touch created && stat -c %u:%g created && sleep 60",
+        ])
+        .with_workdir("/tmp")
+        .with_user("1000:1000")
+        .wait_for_log_on_startup("1000:1000")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container.exec(&["pwd"]).await?;
+    assert_eq!(result.stdout.trim(), "/tmp");
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_keep_postgresql_data_on_tmpfs() -> Result<(), Error> {
+    let container = postgresql()
+        .await
+        .with_tmpfs("/var/lib/postgresql/data", "rw,size=256m")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container
+        .exec(&["sh", "-c", "grep /var/lib/postgresql/data /proc/mounts"])
+        .await?;
+    assert!(result.stdout.starts_with("tmpfs"));
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_bind_mount_read_only_directory() -> Result<(), Error> {
+    let host_dir =
+        std::env::temp_dir().join(format!("testcontainers-bind-{}", Session::current().id()));
+    std::fs::create_dir_all(&host_dir)?;
+    std::fs::write(host_dir.join("greeting.txt"), "hello")?;
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_bind_mount(&host_dir, "/data", BindMode::ReadOnly)
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container.exec(&["cat", "/data/greeting.txt"]).await?;
+    assert_eq!(result.stdout, "hello");
+    let result = container.exec(&["touch", "/data/other.txt"]).await?;
+    assert_ne!(result.exit_code, 0);
+    container.stop_and_remove().await?;
+    std::fs::remove_dir_all(host_dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_share_named_volume_between_containers() -> Result<(), Error> {
+    let volume = Volume::create(format!("testcontainers-data-{}", Session::current().id())).await?;
+    let inspect = volume.inspect().await?;
+    assert_eq!(
+        inspect.labels.get(SESSION_ID_LABEL).map(String::as_str),
+        Some(Session::current().id())
+    );
+    let writer = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sh", "-c", "echo seeded > /data/state"])
+        .with_volume_mount(&volume, "/data")
+        .create()
+        .await?
+        .run_to_completion()
+        .await?;
+    assert_eq!(writer.exit_code, 0);
+    let reader = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["cat", "/data/state"])
+        .with_volume_mount(&volume, "/data")
+        .create()
+        .await?;
+    let result = reader.run_to_completion().await?;
+    assert_eq!(result.stdout.trim(), "seeded");
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_mount_volumes_from_another_container() -> Result<(), Error> {
+    let seed = GenericContainer::from_dockerfile_content(
+        "FROM alpine:3.19\nRUN mkdir /fixtures && echo seeded > /fixtures/state\nVOLUME /fixtures",
+    )
+    .with_command(&["sleep", "60"])
+    .create()
+    .await?
+    .start()
+    .await?;
+    let result = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["cat", "/fixtures/state"])
+        .with_volumes_from(&seed)
+        .create()
+        .await?
+        .run_to_completion()
+        .await?;
+    assert_eq!(result.stdout.trim(), "seeded");
+    seed.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_apply_ulimits() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sh", "-c", "ulimit -n && sleep 60"])
+        .with_ulimit("nofile", 4096, 8192)
+        .wait_for_log_on_startup("4096")
+        .create()
+        .await?
+        .start()
+        .await?;
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_configure_dns_resolution() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_dns("10.0.0.53")
+        .with_dns_search("corp.example")
+        .with_dns_option("ndots:2")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let resolv_conf = container
+        .copy_file_from_container("/etc/resolv.conf")
+        .await?;
+    let resolv_conf = String::from_utf8_lossy(&resolv_conf);
+    assert!(resolv_conf.contains("nameserver 10.0.0.53"));
+    assert!(resolv_conf.contains("search corp.example"));
+    assert!(resolv_conf.contains("options ndots:2"));
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_map_host_devices() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_device("/dev/null", "/dev/host-null", "rwm")
+        .with_device_cgroup_rule("c 1:3 rwm")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container
+        .exec(&["sh", "-c", "echo data > /dev/host-null"])
+        .await?;
+    assert_eq!(result.exit_code, 0);
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_select_oci_runtime() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_oci_runtime("runc")
+        .create()
+        .await?;
+    let inspect = container.inspect().await?;
+    let host_config = inspect.host_config.unwrap_or_default();
+    assert_eq!(host_config["Runtime"], "runc");
+    container.remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_apply_security_options() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_security_opt("seccomp=unconfined")
+        .with_security_opt("no-new-privileges")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container
+        .exec(&[
+            "sh",
+            "-c",
+            "grep -E '^(Seccomp|NoNewPrivs):' /proc/self/status",
+        ])
+        .await?;
+    assert!(result.stdout.contains("NoNewPrivs:\t1"));
+    assert!(result.stdout.contains("Seccomp:\t0"));
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_wait_for_port_on_host_network() -> Result<(), Error> {
+    let redis = GenericContainer::from_image("redis:7")
+        .with_network_mode("host")
+        .with_command(&["redis-server", "--port", "16379"])
+        .with_wait_strategy_on_startup(ReadyStrategy::PortListening("16379/tcp".into()))
+        .create()
+        .await?
+        .start()
+        .await?;
+    assert_eq!(redis.get_host_port("16379/tcp"), Some(16379));
+    redis.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_share_pid_namespace_with_another_container() -> Result<(), Error> {
+    let target = GenericContainer::from_image("redis:7")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let debugger = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_pid_mode(format!("container:{}", target.id()))
+        .with_ipc_mode("private")
+        .with_uts_mode("host")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = debugger.exec(&["ps", "-o", "comm"]).await?;
+    assert!(result.stdout.contains("redis-server"));
+    debugger.stop_and_remove().await?;
+    target.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_stop_with_custom_signal() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&[
+            "sh",
+            "-c",
+            "trap 'echo graceful shutdown; exit 0' INT; echo started; while true; do sleep 1; done",
+        ])
+        .with_stop_signal("SIGINT")
+        .with_stop_timeout(Duration::from_secs(30))
+        .wait_for_log_on_startup("started")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let stopped = container.stop().await?;
+    assert!(stopped.logs().await?.contains("graceful shutdown"));
+    stopped.remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_run_init_process_with_oom_score() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_init(true)
+        .with_oom_score_adj(500)
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container.exec(&["cat", "/proc/1/comm"]).await?;
+    assert_ne!(result.stdout.trim(), "sleep");
+    let result = container.exec(&["cat", "/proc/1/oom_score_adj"]).await?;
+    assert_eq!(result.stdout.trim(), "500");
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_add_supplementary_groups() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_user("1000")
+        .with_group_add("2000")
+        .with_group_add("wheel")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container.exec(&["id", "-G"]).await?;
+    let groups: Vec<_> = result.stdout.split_whitespace().collect();
+    assert!(groups.contains(&"2000"));
+    assert!(groups.contains(&"10"));
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_assign_static_addresses_on_user_network() -> Result<(), Error> {
+    let network = Network::create_with_subnets(
+        "testcontainers-static-addresses",
+        &["172.31.250.0/24", "fd00:dead:beef::/64"],
+    )
+    .await?;
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .with_network(&network)
+        .with_ipv4_address("172.31.250.10")
+        .with_ipv6_address("fd00:dead:beef::10")
+        .with_mac_address("02:42:ac:1f:fa:0a")
+        .create()
+        .await?
+        .start()
+        .await?;
+    assert_eq!(
+        container.get_container_ip(network.name()).as_deref(),
+        Some("172.31.250.10")
+    );
+    let result = container.exec(&["ip", "addr", "show", "eth0"]).await?;
+    assert!(result.stdout.contains("fd00:dead:beef::10"));
+    assert!(result.stdout.contains("02:42:ac:1f:fa:0a"));
+    container.stop_and_remove().await?;
+    network.remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_refuse_static_address_without_network() {
+    let result = GenericContainer::from_image("alpine:3.19")
+        .with_ipv4_address("172.31.250.10")
+        .create()
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfiguration(_))));
+}
+
+#[tokio::test]
+async fn should_expose_udp_port() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["nc", "-lu", "-p", "5353"])
+        .add_exposed_udp_port(5353)
+        .add_exposed_tcp_port(8080)
+        .create()
+        .await?
+        .start()
+        .await?;
+    let udp_port = container.get_host_port(ContainerPort::udp(5353));
+    assert!(udp_port.is_some());
+    assert_eq!(udp_port, container.get_host_port("5353/udp"));
+    assert_eq!(
+        container.get_host_port("8080"),
+        container.get_host_port("8080/tcp")
+    );
+    assert_eq!(container.get_host_port(ContainerPort::tcp(5353)), None);
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_expose_port_range() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["sleep", "60"])
+        .add_exposed_port_range(30000..=30004, Protocol::Tcp)
+        .create()
+        .await?
+        .start()
+        .await?;
+    let host_ports = container
+        .get_host_port_range(30000..=30004, Protocol::Tcp)
+        .unwrap();
+    assert_eq!(host_ports.len(), 5);
+    assert_eq!(Some(host_ports[2]), container.get_host_port("30002/tcp"));
+    assert_eq!(
+        container.get_host_port_range(30000..=30005, Protocol::Tcp),
+        None
+    );
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_explain_missing_port_mappings() -> Result<(), Error> {
+    let container = GenericContainer::from_image("redis:7")
+        .add_exposed_tcp_port(6379)
+        .create()
+        .await?
+        .start()
+        .await?;
+    let host_port = container.try_get_host_port("6379/tcp")?;
+    assert_eq!(container.get_first_mapped_port(), Ok(host_port));
+    assert_eq!(
+        container.try_get_host_port(ContainerPort::tcp(8080)),
+        Err(PortError::NotExposed(ContainerPort::tcp(8080)))
+    );
+    assert!(matches!(
+        container.try_get_host_port("redis"),
+        Err(PortError::InvalidSpec(_))
+    ));
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_reach_server_of_test_process_from_container() -> Result<(), Error> {
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:0").await?;
+    let local_port = listener.local_addr()?.port();
+    let server = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        stream.write_all(b"hello from the host").await.unwrap();
+    });
+    let network = Network::create("testcontainers-host-ports").await?;
+    let exposed = network.expose_host_port(local_port).await?;
+    let result = GenericContainer::from_image("alpine:3.19")
+        .with_command(&["nc", exposed.host(), &local_port.to_string()])
+        .with_network(&network)
+        .create()
+        .await?
+        .run_to_completion()
+        .await?;
+    assert_eq!(result.stdout, "hello from the host");
+    server.await.unwrap();
+    exposed.stop().await?;
+    Ok(())
+}
+
+#[cfg(feature = "raw-api")]
+#[tokio::test]
+async fn should_give_access_to_docker_api_handles() -> Result<(), Error> {
+    let container = GenericContainer::from_image("redis:7")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let top = container.raw_container().top(None).await?;
+    assert!(!top.processes.unwrap_or_default().is_empty());
+    let info = container.docker().info().await?;
+    assert!(info.containers_running.unwrap_or_default() > 0);
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_create_mysql_container() -> Result<(), Error> {
+    let container: MySqlContainer = mysql().await.create().await?.start().await?.into();
+    let port = container.get_host_port().unwrap();
+    assert_eq!(
+        container.connection_url(),
+        format!("mysql://test:test@{}:{port}/test", container.get_host())
+    );
+    let result = container
+        .exec(&["mysql", "-utest", "-ptest", "test", "-e", "SELECT 1"])
+        .await?;
+    assert_eq!(result.exit_code, 0);
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[cfg(any(feature = "diesel-mysql", feature = "diesel-postgres"))]
+const DIESEL_MIGRATIONS: diesel_migrations::EmbeddedMigrations =
+    diesel_migrations::embed_migrations!("tests/migrations");
+
+// diesel is synchronous, the connections block the test runtime while they are used
+#[cfg(feature = "diesel-postgres")]
+#[tokio::test]
+async fn should_run_diesel_migrations_on_postgresql() -> Result<(), Error> {
+    use diesel::{sql_query, RunQueryDsl};
+
+    let container: PostgresContainer = postgresql().await.create().await?.start().await?.into();
+    container.run_diesel_migrations(DIESEL_MIGRATIONS)?;
+    container.run_diesel_migrations(DIESEL_MIGRATIONS)?;
+    let mut connection = container.establish_diesel_connection()?;
+    let inserted = sql_query("INSERT INTO users (name) VALUES ('ada')")
+        .execute(&mut connection)
+        .unwrap();
+    assert_eq!(1, inserted);
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[cfg(feature = "diesel-mysql")]
+#[tokio::test]
+async fn should_run_diesel_migrations_on_mysql() -> Result<(), Error> {
+    use diesel::{sql_query, RunQueryDsl};
+
+    let container: MySqlContainer = mysql().await.create().await?.start().await?.into();
+    container.run_diesel_migrations(DIESEL_MIGRATIONS)?;
+    let mut connection = container.establish_diesel_connection()?;
+    let inserted = sql_query("INSERT INTO users (name) VALUES ('ada')")
+        .execute(&mut connection)
+        .unwrap();
+    assert_eq!(1, inserted);
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_create_redis_container_with_password() -> Result<(), Error> {
+    let container: RedisContainer = redis()
+        .await
+        .add_env("REDIS_PASSWORD", "secret")
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let port = container.get_host_port().unwrap();
+    assert_eq!(
+        container.connection_url(),
+        format!("redis://:secret@{}:{port}", container.get_host())
+    );
+    let result = container.exec(&["redis-cli", "ping"]).await?;
+    assert!(result.stdout.contains("NOAUTH"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_start_redis_stack_module() -> Result<(), Error> {
+    let container: RedisContainer = ModuleBuilder::new(Redis::stack())
+        .with(|builder| builder.add_env("REDIS_PASSWORD", "secret"))
+        .start()
+        .await?;
+    let result = container
+        .exec(&[
+            "redis-cli",
+            "-a",
+            "secret",
+            "--no-auth-warning",
+            "MODULE",
+            "LIST",
+        ])
+        .await?;
+    assert!(result.stdout.contains("search"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[testcontainers::test(cache = redis(), db = postgresql())]
+async fn should_inject_started_containers(cache: RedisContainer, db: PostgresContainer) {
+    let result = cache.exec(&["redis-cli", "ping"]).await.unwrap();
+    assert_eq!(result.stdout.trim(), "PONG");
+    let result = db.exec(&["pg_isready"]).await.unwrap();
+    assert_eq!(result.exit_code, 0);
+}
+
+#[tokio::test]
+async fn should_start_container_from_definition_file() -> Result<(), Error> {
+    let definition_file = std::env::temp_dir().join("testcontainers.toml");
+    std::fs::write(
+        &definition_file,
+        r#"
+        image = "redis:7"
+        ports = ["6379"]
+
+        [wait]
+        command = ["redis-cli", "ping"]
+        "#,
+    )?;
+    let container = GenericContainerBuilder::from_file(&definition_file)?
+        .create()
+        .await?
+        .start()
+        .await?;
+    assert!(container.get_host_port("6379/tcp").is_some());
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[test]
+fn should_run_container_without_async_runtime() -> Result<(), Error> {
+    let builder = blocking::block_on(redis());
+    let container = blocking::GenericContainer::create(builder)?.start()?;
+    assert!(container.get_host_port("6379/tcp").is_some());
+    let result = container.exec(&["redis-cli", "ping"])?;
+    assert_eq!(result.stdout.trim(), "PONG");
+    container.stop_and_remove()?;
+    Ok(())
+}
+
+static SHARED_POSTGRES: SharedContainer<PostgresContainer> = SharedContainer::new();
+
+#[tokio::test]
+async fn should_start_shared_container_once() -> Result<(), Error> {
+    let first = SHARED_POSTGRES.get_or_init(postgresql()).await?;
+    let second = SHARED_POSTGRES.get_or_init(postgresql()).await?;
+    assert!(std::ptr::eq(first, second));
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_use_shared_container_from_another_test() -> Result<(), Error> {
+    let postgres = SHARED_POSTGRES.get_or_init(postgresql()).await?;
+    let result = postgres.exec(&["pg_isready"]).await?;
+    assert_eq!(result.exit_code, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_advertise_mapped_kafka_port() -> Result<(), Error> {
+    let container: KafkaContainer = kafka().await.create().await?.start().await?.into();
+    let port = container.get_host_port("9092/tcp").unwrap();
+    assert_eq!(
+        container.bootstrap_servers(),
+        format!("{}:{port}", container.get_host())
+    );
+    let result = container
+        .exec(&[
+            "/opt/kafka/bin/kafka-topics.sh",
+            "--bootstrap-server",
+            "localhost:9093",
+            "--create",
+            "--topic",
+            "testcontainers",
+        ])
+        .await?;
+    assert_eq!(result.exit_code, 0);
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_initiate_mongodb_replica_set() -> Result<(), Error> {
+    let container: MongoDbContainer = mongodb_replica_set()
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let port = container.get_host_port().unwrap();
+    assert_eq!(
+        container.connection_string(),
+        format!(
+            "mongodb://{}:{port}/?directConnection=true",
+            container.get_host()
+        )
+    );
+    let result = container
+        .exec(&["mongosh", "--quiet", "--eval", "rs.status().set"])
+        .await?;
+    assert_eq!(result.stdout.trim(), "rs0");
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_declare_rabbitmq_topology() -> Result<(), Error> {
+    let container: RabbitMqContainer = rabbitmq().await.create().await?.start().await?.into();
+    container.declare_vhost("orders").await?;
+    container.declare_user("app", "secret", "").await?;
+    container.grant_permissions("orders", "app").await?;
+    container
+        .declare_exchange("orders", "events", "topic")
+        .await?;
+    container.declare_queue("orders", "created").await?;
+    container
+        .declare_binding("orders", "events", "created", "order.created")
+        .await?;
+    let result = container
+        .exec(&["rabbitmqctl", "list_bindings", "-p", "orders"])
+        .await?;
+    assert!(result.stdout.contains("order.created"));
+    assert!(container.amqp_url().starts_with("amqp://test:test@"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_start_a_secured_elasticsearch_node() -> Result<(), Error> {
+    let container: ElasticsearchContainer = elasticsearch_secured()
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    assert_eq!(Some("elastic"), container.username());
+    assert!(container
+        .password()
+        .is_some_and(|password| !password.is_empty()));
+    assert!(container.http_url().starts_with("http://"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_only_expose_selected_localstack_services() -> Result<(), Error> {
+    let container: LocalStackContainer =
+        localstack_with_services(&[LocalStackService::S3, LocalStackService::Sqs])
+            .await
+            .create()
+            .await?
+            .start()
+            .await?
+            .into();
+    assert!(container.endpoint_url(LocalStackService::S3).is_some());
+    assert!(container.endpoint_url(LocalStackService::Sqs).is_some());
+    assert_eq!(None, container.endpoint_url(LocalStackService::DynamoDb));
+    assert_eq!("us-east-1", container.region());
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[cfg(feature = "aws-config")]
+#[tokio::test]
+async fn should_configure_aws_sdk_for_localstack() -> Result<(), Error> {
+    let container: LocalStackContainer = localstack_with_services(&[LocalStackService::S3])
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let config = container.sdk_config();
+    assert_eq!(
+        Some("us-east-1"),
+        config.region().map(|region| region.as_ref())
+    );
+    assert_eq!(
+        container.endpoint_url(LocalStackService::S3).as_deref(),
+        config.endpoint_url()
+    );
+    assert!(config.credentials_provider().is_some());
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_create_minio_buckets() -> Result<(), Error> {
+    let container: MinioContainer = minio().await.create().await?.start().await?.into();
+    container.create_bucket("uploads").await?;
+    container.create_bucket("uploads").await?;
+    let result = container.exec(&["ls", "/data"]).await?;
+    assert!(result.stdout.contains("uploads"));
+    assert_eq!("minioadmin", container.access_key());
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_import_keycloak_realm() -> Result<(), Error> {
+    let realm_file = std::env::temp_dir().join("testcontainers-realm.json");
+    std::fs::write(&realm_file, r#"{"realm": "demo", "enabled": true}"#)?;
+    let container: KeycloakContainer = keycloak_with_realm_import(&realm_file)
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    assert!(container.logs().await?.contains("Realm 'demo' imported"));
+    assert_eq!(
+        format!("{}/realms/demo", container.auth_server_url()),
+        container.issuer_url("demo")
+    );
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_enable_nats_jetstream() -> Result<(), Error> {
+    let container: NatsContainer = nats_with_jetstream()
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    assert!(container.logs().await?.contains("Starting JetStream"));
+    assert!(container.connection_url().starts_with("nats://"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_create_pulsar_topics() -> Result<(), Error> {
+    let container: PulsarContainer = pulsar().await.create().await?.start().await?.into();
+    container.create_tenant("acme").await?;
+    container.create_namespace("acme", "orders").await?;
+    container.create_topic("acme", "orders", "created").await?;
+    container
+        .create_partitioned_topic("acme", "orders", "updated", 3)
+        .await?;
+    let result = container
+        .exec(&["bin/pulsar-admin", "topics", "list", "acme/orders"])
+        .await?;
+    assert!(result.stdout.contains("persistent://acme/orders/created"));
+    assert!(container.broker_url().starts_with("pulsar://"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_advertise_etcd_mapped_client_port() -> Result<(), Error> {
+    let container: EtcdContainer = etcd().await.create().await?.start().await?.into();
+    let result = container
+        .exec(&["etcdctl", "member", "list", "--write-out=simple"])
+        .await?;
+    assert!(result.stdout.contains(&container.client_endpoint()));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_register_consul_kv_and_services() -> Result<(), Error> {
+    let container: ConsulContainer = consul().await.create().await?.start().await?.into();
+    container.put_kv("config/feature", "enabled").await?;
+    container
+        .register_service("payments", "10.0.0.12", 8080)
+        .await?;
+    let value = container
+        .exec(&["consul", "kv", "get", "config/feature"])
+        .await?;
+    assert_eq!("enabled", value.stdout.trim());
+    let services = container.exec(&["consul", "catalog", "services"]).await?;
+    assert!(services.stdout.contains("payments"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_register_wiremock_stubs() -> Result<(), Error> {
+    let container: WireMockContainer = wiremock().await.create().await?.start().await?.into();
+    let id = container
+        .stub_json_response("GET", "/users/1", 200, serde_json::json!({ "name": "ada" }))
+        .await?;
+    assert!(!id.is_empty());
+    let uri = format!("{}/users/1", container.base_url()).parse().unwrap();
+    let response = hyper::Client::new().get(uri).await.unwrap();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let user: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!("ada", user["name"]);
+    container.reset().await?;
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_expose_k3s_kubeconfig() -> Result<(), Error> {
+    let container: K3sContainer = k3s().await.create().await?.start().await?.into();
+    let kubeconfig = container.kubeconfig().await?;
+    assert!(kubeconfig.contains(&format!("server: {}", container.server_url())));
+    let nodes = container.exec(&["kubectl", "get", "nodes"]).await?;
+    assert!(nodes.stdout.contains("Ready"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_pull_ollama_model() -> Result<(), Error> {
+    let container: OllamaContainer = ollama().await.create().await?.start().await?.into();
+    container.pull_model("all-minilm").await?;
+    let models = container.exec(&["ollama", "list"]).await?;
+    assert!(models.stdout.contains("all-minilm"));
+    assert!(container.openai_base_url().ends_with("/v1"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_fetch_mailpit_received_emails() -> Result<(), Error> {
+    let container: MailpitContainer = mailpit().await.create().await?.start().await?.into();
+    let (host, port) = container.smtp_host_and_port();
+    let mut smtp = tokio::io::BufStream::new(tokio::net::TcpStream::connect((host, port)).await?);
+    let mut line = String::new();
+    smtp.read_line(&mut line).await?;
+    for command in [
+        "HELO localhost",
+        "MAIL FROM:<app@example.com>",
+        "RCPT TO:<user@example.com>",
+        "DATA",
+        "Subject: Welcome\r\n\r\nHello there\r\n.",
+        "QUIT",
+    ] {
+        smtp.write_all(format!("{command}\r\n").as_bytes()).await?;
+        smtp.flush().await?;
+        line.clear();
+        smtp.read_line(&mut line).await?;
+    }
+    let emails = container.wait_for_emails(1, Duration::from_secs(5)).await?;
+    assert_eq!("app@example.com", emails[0].from);
+    assert_eq!(vec!["user@example.com"], emails[0].to);
+    assert_eq!("Welcome", emails[0].subject);
+    assert_eq!("Hello there", emails[0].text.trim());
+    container.delete_all_emails().await?;
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_seed_openldap_from_ldif() -> Result<(), Error> {
+    let ldif_file = std::env::temp_dir().join("testcontainers-seed.ldif");
+    std::fs::write(
+        &ldif_file,
+        "dn: dc=example,dc=org\nobjectClass: dcObject\nobjectClass: organization\ndc: example\no: example\n\n\
+         dn: ou=people,dc=example,dc=org\nobjectClass: organizationalUnit\nou: people\n",
+    )?;
+    let container: OpenLdapContainer = openldap_with_ldif(&ldif_file)
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let result = container
+        .exec(&[
+            "ldapsearch",
+            "-x",
+            "-H",
+            "ldap://localhost:1389",
+            "-D",
+            &container.admin_dn(),
+            "-w",
+            container.admin_password(),
+            "-b",
+            container.base_dn(),
+            "ou=people",
+        ])
+        .await?;
+    assert!(result.stdout.contains("dn: ou=people,dc=example,dc=org"));
+    assert!(container.ldap_url().starts_with("ldap://"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_start_sftp_server_with_password() -> Result<(), Error> {
+    let container: SftpContainer = sftp_server("alice", "secret")
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    assert_eq!("alice", container.username());
+    assert_eq!("secret", container.password());
+    assert!(container
+        .host_public_key()
+        .await?
+        .starts_with("ssh-ed25519 "));
+    let home = container.exec(&["ls", "/home/alice"]).await?;
+    assert!(home.stdout.contains("upload"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_start_activemq_artemis() -> Result<(), Error> {
+    let container: ArtemisContainer = activemq_artemis()
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let result = container
+        .exec(&[
+            "/var/lib/artemis-instance/bin/artemis",
+            "queue",
+            "stat",
+            "--user",
+            container.username(),
+            "--password",
+            container.password(),
+        ])
+        .await?;
+    assert_eq!(0, result.exit_code);
+    assert!(container.broker_url().starts_with("tcp://"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_accept_scylla_cql_queries() -> Result<(), Error> {
+    let container: ScyllaContainer = scylla().await.create().await?.start().await?.into();
+    let result = container
+        .exec(&["cqlsh", "-e", "SELECT release_version FROM system.local"])
+        .await?;
+    assert_eq!(0, result.exit_code);
+    assert!(container
+        .contact_point()
+        .ends_with(&format!(":{}", container.get_host_port().unwrap())));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_serve_nginx_static_content() -> Result<(), Error> {
+    let content_dir = std::env::temp_dir().join("testcontainers-nginx");
+    std::fs::create_dir_all(content_dir.join("api"))?;
+    std::fs::write(content_dir.join("api/status.json"), r#"{"status":"up"}"#)?;
+    let container: NginxContainer = nginx_with_static_content(&content_dir)
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let uri = format!("{}/api/status.json", container.base_url())
+        .parse()
+        .unwrap();
+    let response = hyper::Client::new().get(uri).await.unwrap();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(r#"{"status":"up"}"#.as_bytes(), body);
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_configure_trino_catalogs() -> Result<(), Error> {
+    let properties_file = std::env::temp_dir().join("testcontainers-memory.properties");
+    std::fs::write(&properties_file, "connector.name=memory\n")?;
+    let container: TrinoContainer = trino_with_catalogs(&[("scratch", &properties_file)])
+        .await
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let result = container
+        .exec(&["trino", "--execute", "SHOW CATALOGS"])
+        .await?;
+    assert!(result.stdout.contains("scratch"));
+    assert!(container.jdbc_url().starts_with("jdbc:trino://"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_run_module_after_start_hook() -> Result<(), Error> {
+    let container: MinioContainer = ModuleBuilder::new(Minio::default().with_bucket("reports"))
+        .with(|builder| builder.add_env("MINIO_ROOT_PASSWORD", "s3cr3t-key"))
+        .start()
+        .await?;
+    let result = container.exec(&["ls", "/data"]).await?;
+    assert!(result.stdout.contains("reports"));
+    assert_eq!("s3cr3t-key", container.secret_key());
+    container.into_inner().kill().await?;
     Ok(())
 }
 
 #[tokio::test]
 async fn should_expose_postgresql_ports() -> Result<(), Error> {
     let container = postgresql().await.create().await?;
-    container.start().await?;
+    let container = container.start().await?;
 
+    let host = container.get_host();
     let port = container.get_host_port("5432/tcp").unwrap();
 
-    let params = format!("host=localhost port={port} dbname=test user=test password=test");
+    let params = format!("host={host} port={port} dbname=test user=test password=test");
     let (client, conn) = tokio_postgres::connect(&params, NoTls).await.unwrap();
     tokio::spawn(conn);
     let databases: Vec<String> = client
@@ -33,3 +1176,785 @@ async fn should_expose_postgresql_ports() -> Result<(), Error> {
     container.kill().await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn should_install_postgresql_extensions() -> Result<(), Error> {
+    for (builder, extension) in [
+        (timescaledb().await, "timescaledb"),
+        (pgvector().await, "vector"),
+    ] {
+        let container = builder.create().await?.start().await?;
+        let host = container.get_host();
+        let port = container.get_host_port("5432/tcp").unwrap();
+        let params = format!("host={host} port={port} dbname=test user=test password=test");
+        let (client, conn) = tokio_postgres::connect(&params, NoTls).await.unwrap();
+        tokio::spawn(conn);
+        let installed = client
+            .query(
+                "SELECT 1 FROM pg_extension WHERE extname = $1",
+                &[&extension],
+            )
+            .await
+            .unwrap();
+        assert_eq!(1, installed.len());
+        container.kill().await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_run_postgresql_init_scripts() -> Result<(), Error> {
+    let init_file = std::env::temp_dir().join("testcontainers-init.sql");
+    std::fs::write(&init_file, "INSERT INTO users VALUES ('ada');")?;
+    let container: PostgresContainer = ModuleBuilder::new(
+        Postgres::default()
+            .with_database("app")
+            .with_username("app")
+            .with_password("secret")
+            .with_init_sql("CREATE TABLE users (name text);")
+            .with_init_sql(init_file),
+    )
+    .start()
+    .await?;
+    assert_eq!(
+        format!(
+            "postgres://app:secret@{}:{}/app",
+            container.get_host(),
+            container.get_host_port().unwrap()
+        ),
+        container.connection_string()
+    );
+    let (client, conn) = tokio_postgres::connect(&container.connection_string(), NoTls)
+        .await
+        .unwrap();
+    tokio::spawn(conn);
+    let users = client.query("SELECT name FROM users", &[]).await.unwrap();
+    assert_eq!(1, users.len());
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_clone_postgresql_template_databases() -> Result<(), Error> {
+    let container: PostgresContainer =
+        ModuleBuilder::new(Postgres::default().with_init_sql("CREATE TABLE users (name text);"))
+            .start()
+            .await?;
+    let first = container.create_database_from_template("first").await?;
+    let second = container.create_database_from_template("second").await?;
+    for database in [&first, &second] {
+        let (client, conn) = tokio_postgres::connect(database.connection_string(), NoTls)
+            .await
+            .unwrap();
+        tokio::spawn(conn);
+        client
+            .execute("INSERT INTO users VALUES ($1)", &[&database.name()])
+            .await
+            .unwrap();
+        let users = client.query("SELECT name FROM users", &[]).await.unwrap();
+        assert_eq!(1, users.len());
+    }
+    first.remove().await?;
+    let third = container
+        .create_database_from_template("third \"quoted\"")
+        .await?;
+    let (client, conn) = tokio_postgres::connect(third.connection_string(), NoTls)
+        .await
+        .unwrap();
+    tokio::spawn(conn);
+    let users = client.query("SELECT name FROM users", &[]).await.unwrap();
+    assert!(users.is_empty());
+    let list_databases = || {
+        container.exec(&[
+            "psql",
+            "-U",
+            "test",
+            "-d",
+            "postgres",
+            "-tAc",
+            "SELECT datname FROM pg_database",
+        ])
+    };
+    let databases = list_databases().await?.stdout;
+    assert!(!databases.contains("first"));
+    assert!(databases.contains("second"));
+    assert!(databases.contains(third.name()));
+    // the database of a dropped handle goes away in the background, even once the runtime that
+    // dropped it has ended like the one of a #[tokio::test]
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async move { drop(second) });
+    })
+    .join()
+    .unwrap();
+    let mut databases = list_databases().await?.stdout;
+    for _ in 0..50 {
+        if !databases.contains("second") {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        databases = list_databases().await?.stdout;
+    }
+    assert!(!databases.contains("second"));
+    container.into_inner().kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_wait_for_postgresql_port_listening() -> Result<(), Error> {
+    let container = postgresql()
+        .await
+        .wait_for_port_listening(5432)
+        .create()
+        .await?;
+    let container = container.start().await?;
+    assert!(container.get_host_port("5432/tcp").is_some());
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_wait_for_postgresql_command_success() -> Result<(), Error> {
+    let container = postgresql()
+        .await
+        .wait_for_command_on_startup(&["pg_isready", "-U", "test", "-h", "localhost"])
+        .create()
+        .await?;
+    let container = container.start().await?;
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_wait_for_all_postgresql_strategies() -> Result<(), Error> {
+    let container = postgresql()
+        .await
+        .with_wait_strategy_on_startup(ReadyStrategy::All(vec![
+            ReadyStrategy::PortListening("5432/tcp".into()),
+            ReadyStrategy::ExecCommand(vec!["pg_isready".into(), "-h".into(), "localhost".into()]),
+        ]))
+        .create()
+        .await?;
+    let container = container.start().await?;
+    container.kill().await?;
+    Ok(())
+}
+
+struct DatabaseExists(&'static str);
+
+#[async_trait]
+impl WaitStrategy for DatabaseExists {
+    async fn is_ready(&self, container: &ContainerView) -> Result<bool, Error> {
+        let result = container
+            .exec(&["psql", "-U", "test", "-h", "localhost", "-lqt"])
+            .await?;
+        Ok(result.exit_code == 0 && result.stdout.contains(self.0))
+    }
+}
+
+#[tokio::test]
+async fn should_wait_for_custom_strategy() -> Result<(), Error> {
+    let container = postgresql()
+        .await
+        .wait_for(DatabaseExists("test"))
+        .create()
+        .await?;
+    let container = container.start().await?;
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_wait_for_postgresql_pid_file() -> Result<(), Error> {
+    let container = postgresql()
+        .await
+        .wait_for_file_on_startup("/var/lib/postgresql/data/postmaster.pid")
+        .create()
+        .await?;
+    let container = container.start().await?;
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_run_container_to_completion() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sh", "-c", "echo out; echo err >&2; exit 3"])
+        .create()
+        .await?;
+    let result = container.run_to_completion().await?;
+    assert_eq!(result.exit_code, 3);
+    assert_eq!(result.stdout, "out\n");
+    assert_eq!(result.stderr, "err\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_wait_for_log_on_selected_stream() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sh", "-c", "echo ready >&2; sleep 30"])
+        .wait_for_log_on_startup_with("ready", LogOptions::stderr())
+        .create()
+        .await?;
+    let container = container.start().await?;
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_start_container_group_concurrently() -> Result<(), Error> {
+    let group = ContainerGroup::new()
+        .add("first", postgresql().await)
+        .add("second", postgresql().await)
+        .start()
+        .await?;
+    assert!(group
+        .get("first")
+        .unwrap()
+        .get_host_port("5432/tcp")
+        .is_some());
+    assert!(group
+        .get("second")
+        .unwrap()
+        .get_host_port("5432/tcp")
+        .is_some());
+    group.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_start_dependencies_first() -> Result<(), Error> {
+    let database = postgresql().await;
+    let client = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sleep", "30"])
+        .depends_on_with(
+            &database,
+            ReadyStrategy::ExecCommand(vec!["pg_isready".into(), "-h".into(), "localhost".into()]),
+        );
+    let group = ContainerGroup::new()
+        .add("client", client)
+        .add("database", database)
+        .start()
+        .await?;
+    let started: Vec<_> = group.iter().map(|(name, _)| name).collect();
+    assert_eq!(started, ["database", "client"]);
+    group.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_remove_group_member_failing_to_start() -> Result<(), Error> {
+    let broken = GenericContainer::from_image("alpine:latest")
+        .with_container_name("testcontainers-group-broken-member")
+        .with_command(&["false"])
+        .wait_for_log_on_startup("never printed");
+    let result = ContainerGroup::new()
+        .add("database", postgresql().await)
+        .add("broken", broken)
+        .start()
+        .await;
+    assert!(matches!(result, Err(Error::StartupExited { .. })));
+    // the name is free again, otherwise a random suffix would be appended
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_container_name("testcontainers-group-broken-member")
+        .create()
+        .await?;
+    assert_eq!(
+        container.name().await?,
+        "testcontainers-group-broken-member"
+    );
+    container.remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_resolve_network_aliases() -> Result<(), Error> {
+    let network = Network::create("testcontainers-network-aliases").await?;
+    let database = postgresql()
+        .await
+        .with_network(&network)
+        .with_network_alias("db")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = GenericContainer::from_image("postgres:latest")
+        .with_network(&network)
+        .with_command(&["pg_isready", "-h", "db"])
+        .create()
+        .await?
+        .run_to_completion()
+        .await?;
+    assert_eq!(result.exit_code, 0);
+    assert!(database.get_container_ip(network.name()).is_some());
+    assert!(database.get_gateway_ip().is_some());
+    database.stop_and_remove().await?;
+    network.remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_inject_toxiproxy_latency() -> Result<(), Error> {
+    let network = Network::create("testcontainers-toxiproxy").await?;
+    let redis = redis()
+        .await
+        .with_network(&network)
+        .with_network_alias("cache")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let toxiproxy: ToxiproxyContainer = toxiproxy()
+        .await
+        .with_network(&network)
+        .create()
+        .await?
+        .start()
+        .await?
+        .into();
+    let proxy = toxiproxy.create_proxy("redis", "cache:6379").await?;
+    let ping = || async {
+        let started = std::time::Instant::now();
+        let mut stream = tokio::net::TcpStream::connect((proxy.host(), proxy.host_port())).await?;
+        stream.write_all(b"PING\r\n").await?;
+        let mut response = [0; 7];
+        stream.read_exact(&mut response).await?;
+        assert_eq!(b"+PONG\r\n", &response);
+        Ok::<_, Error>(started.elapsed())
+    };
+    assert!(ping().await? < Duration::from_millis(500));
+    let toxic = proxy
+        .add_toxic(
+            &Toxic::Latency {
+                latency: Duration::from_millis(500),
+                jitter: Duration::ZERO,
+            },
+            ToxicDirection::Downstream,
+        )
+        .await?;
+    assert!(ping().await? >= Duration::from_millis(500));
+    proxy.remove_toxic(&toxic).await?;
+    toxiproxy.into_inner().kill().await?;
+    redis.kill().await?;
+    network.remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_exec_command_in_running_container() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sleep", "30"])
+        .create()
+        .await?
+        .start()
+        .await?;
+    let result = container
+        .exec_with(
+            &["sh", "-c", "echo $GREETING from $(pwd); exit 2"],
+            &ExecOptions::default()
+                .add_env("GREETING", "hello")
+                .with_working_dir("/tmp"),
+        )
+        .await?;
+    assert_eq!(result.exit_code, 2);
+    assert_eq!(result.stdout, "hello from /tmp\n");
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_copy_files_into_container() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sleep", "30"])
+        .with_copy_string_to_file("staged", "/tmp/staged.txt")
+        .copy_file_to_container("Cargo.toml", "/tmp/Cargo.toml")
+        .create()
+        .await?
+        .start()
+        .await?;
+    container
+        .copy_string_to_file("runtime", "/tmp/runtime.txt")
+        .await?;
+    let result = container
+        .exec(&["cat", "/tmp/staged.txt", "/tmp/runtime.txt"])
+        .await?;
+    assert_eq!(result.stdout, "stagedruntime");
+    let result = container.exec(&["test", "-s", "/tmp/Cargo.toml"]).await?;
+    assert_eq!(result.exit_code, 0);
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_copy_files_from_container() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sleep", "30"])
+        .with_copy_string_to_file("report", "/tmp/out/report.txt")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let contents = container
+        .copy_file_from_container("/tmp/out/report.txt")
+        .await?;
+    assert_eq!(contents, b"report");
+    let archive = container.copy_archive_from_container("/tmp/out").await?;
+    let mut archive = tar::Archive::new(archive.as_slice());
+    let paths = archive
+        .entries()?
+        .map(|entry| entry.unwrap().path().unwrap().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    assert!(paths.contains(&"out/report.txt".to_string()));
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_stream_container_logs() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sh", "-c", "echo one; echo two >&2; sleep 1; echo three"])
+        .wait_for_log_on_startup("one")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let lines = container
+        .log_stream(&LogOptions::stdout().with_follow())
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(lines, vec!["one", "three"]);
+    assert_eq!(container.stderr().await?, "two\n");
+    assert_eq!(container.logs().await?.lines().count(), 3);
+    let tail = container
+        .log_stream(&LogOptions::stdout().with_tail(1))
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(tail, vec!["three"]);
+    container.stop_and_remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_report_logs_when_container_exits_during_startup() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sh", "-c", "echo something went wrong; exit 1"])
+        .wait_for_log_on_startup("ready")
+        .create()
+        .await?;
+    let error = container.start().await.err().unwrap().to_string();
+    assert!(error.contains("exited before being ready"));
+    assert!(error.contains("something went wrong"));
+    Ok(())
+}
+
+#[derive(Default)]
+struct CountingRuntime {
+    inner: DockerClient,
+    created: AtomicUsize,
+    networks: AtomicUsize,
+    volumes: AtomicUsize,
+}
+
+#[async_trait]
+impl ContainerRuntime for CountingRuntime {
+    fn docker_host(&self) -> Option<&str> {
+        self.inner.docker_host()
+    }
+
+    fn daemon_socket(&self) -> &str {
+        self.inner.daemon_socket()
+    }
+
+    async fn image_exists_locally(&self, image: &DockerImage) -> Result<bool, Error> {
+        self.inner.image_exists_locally(image).await
+    }
+
+    async fn image_platform(&self, image: &DockerImage) -> Result<Option<String>, Error> {
+        self.inner.image_platform(image).await
+    }
+
+    async fn pull(
+        &self,
+        image: &DockerImage,
+        platform: Option<&str>,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<(), docker_api::Error> {
+        self.inner.pull(image, platform, auth).await
+    }
+
+    async fn build(
+        &self,
+        image: &DockerImage,
+        platform: Option<&str>,
+        auths: &HashMap<String, RegistryAuth>,
+    ) -> Result<(), docker_api::Error> {
+        self.inner.build(image, platform, auths).await
+    }
+
+    async fn load(&self, archive: &ImageArchive) -> Result<(), docker_api::Error> {
+        self.inner.load(archive).await
+    }
+
+    async fn create(
+        &self,
+        opts: &ContainerCreateOpts,
+        engine_opts: &EngineCreateOpts,
+    ) -> Result<ContainerClient, docker_api::Error> {
+        self.created.fetch_add(1, Ordering::Relaxed);
+        self.inner.create(opts, engine_opts).await
+    }
+
+    async fn create_network(&self, opts: &NetworkCreateOpts) -> Result<DockerNetwork, Error> {
+        self.networks.fetch_add(1, Ordering::Relaxed);
+        self.inner.create_network(opts).await
+    }
+
+    async fn create_volume(&self, opts: &VolumeCreateOpts) -> Result<DockerVolume, Error> {
+        self.volumes.fetch_add(1, Ordering::Relaxed);
+        self.inner.create_volume(opts).await
+    }
+
+    async fn find_running(
+        &self,
+        label: &str,
+        value: &str,
+    ) -> Result<Option<ContainerClient>, Error> {
+        self.inner.find_running(label, value).await
+    }
+
+    async fn list_labeled(&self, label: &str) -> Result<Vec<HashMap<String, String>>, Error> {
+        self.inner.list_labeled(label).await
+    }
+
+    async fn remove_labeled(&self, label: &str, value: &str) -> Result<(), Error> {
+        self.inner.remove_labeled(label, value).await
+    }
+}
+
+#[tokio::test]
+async fn should_create_containers_through_custom_runtime() -> Result<(), Error> {
+    let runtime = Arc::new(CountingRuntime::default());
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sleep", "30"])
+        .with_runtime(runtime.clone())
+        .create()
+        .await?
+        .start()
+        .await?;
+    assert!(runtime.created.load(Ordering::Relaxed) >= 1);
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_create_network_through_custom_runtime() -> Result<(), Error> {
+    let runtime = Arc::new(CountingRuntime::default());
+    let network = Network::create_on(runtime.clone(), "testcontainers-custom-runtime").await?;
+    assert_eq!(runtime.networks.load(Ordering::Relaxed), 1);
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["true"])
+        .with_runtime(runtime.clone())
+        .with_network(&network)
+        .create()
+        .await?;
+    assert_eq!(container.run_to_completion().await?.exit_code, 0);
+    network.remove().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_create_volume_through_custom_runtime() -> Result<(), Error> {
+    let runtime = Arc::new(CountingRuntime::default());
+    let volume = Volume::create_on(runtime.clone(), "testcontainers-custom-runtime").await?;
+    assert_eq!(runtime.volumes.load(Ordering::Relaxed), 1);
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["touch", "/data/file"])
+        .with_runtime(runtime.clone())
+        .with_volume_mount(&volume, "/data")
+        .create()
+        .await?;
+    assert_eq!(container.run_to_completion().await?.exit_code, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_not_pull_missing_image_with_never_pull_policy() -> Result<(), Error> {
+    let result = GenericContainer::from_image("alpine:0.0.0-missing")
+        .with_pull_policy(PullPolicy::Never)
+        .create()
+        .await;
+    assert!(matches!(result, Err(Error::ImageNotFound { .. })));
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_pull_policy(PullPolicy::Always)
+        .with_command(&["true"])
+        .create()
+        .await?;
+    assert_eq!(container.run_to_completion().await?.exit_code, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_run_container_for_requested_platform() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_platform("linux/amd64")
+        .with_command(&["sleep", "30"])
+        .create()
+        .await?
+        .start()
+        .await?;
+    assert_eq!(
+        container.get_platform().await?.as_deref(),
+        Some("linux/amd64")
+    );
+    container.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_build_image_from_dockerfile() -> Result<(), Error> {
+    let context_dir = std::env::temp_dir().join("testcontainers-dockerfile-build");
+    std::fs::create_dir_all(&context_dir)?;
+    std::fs::write(
+        context_dir.join("app.Dockerfile"),
+        "FROM alpine:latest AS base\nARG GREETING\nRUN echo \"$GREETING\" > /greeting\nFROM base AS final\nCMD [\"cat\", \"/greeting\"]\n",
+    )?;
+    let container = GenericContainer::from_dockerfile(&context_dir)
+        .with_dockerfile_name("app.Dockerfile")
+        .with_build_arg("GREETING", "hello")
+        .with_build_target("final")
+        .with_build_label("org.testcontainers.test", "true")
+        .create()
+        .await?;
+    let result = container.run_to_completion().await?;
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(result.stdout.trim(), "hello");
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_build_image_from_in_memory_context() -> Result<(), Error> {
+    let container = GenericContainer::from_dockerfile_content(
+        "FROM alpine:latest\nCOPY conf/app.conf /etc/app.conf\nCMD [\"cat\", \"/etc/app.conf\"]\n",
+    )
+    .with_build_context_file("conf/app.conf", "debug=true")
+    .create()
+    .await?;
+    let result = container.run_to_completion().await?;
+    assert_eq!(result.stdout.trim(), "debug=true");
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_load_image_from_archive() -> Result<(), Error> {
+    let archive_path = std::env::temp_dir().join("testcontainers-archive.tar");
+    let docker = docker_api::Docker::unix("/var/run/docker.sock");
+    GenericContainer::from_image("alpine:latest")
+        .with_command(&["true"])
+        .create()
+        .await?
+        .remove()
+        .await?;
+    docker
+        .images()
+        .get("alpine:latest")
+        .tag(
+            &TagOpts::builder()
+                .repo("testcontainers/archived")
+                .tag("latest")
+                .build(),
+        )
+        .await?;
+    let archived = docker.images().get("testcontainers/archived:latest");
+    let archive: Vec<u8> = archived.export().try_concat().await?;
+    std::fs::write(&archive_path, archive)?;
+    archived.delete().await?;
+    let result = GenericContainer::from_image("testcontainers/archived:latest")
+        .with_image_archive(ImageArchive::new(&archive_path))
+        .with_pull_policy(PullPolicy::Never)
+        .with_command(&["echo", "loaded"])
+        .create()
+        .await?
+        .run_to_completion()
+        .await?;
+    assert_eq!(result.stdout.trim(), "loaded");
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_commit_container_to_reusable_image() -> Result<(), Error> {
+    let container = GenericContainer::from_image("alpine:latest")
+        .with_command(&["sh", "-c", "echo seeded > /seed && sleep 30"])
+        .wait_for_file_on_startup("/seed")
+        .create()
+        .await?
+        .start()
+        .await?;
+    let image = container.commit("testcontainers/seeded:latest").await?;
+    container.kill().await?;
+    let result = GenericContainer::from_image(image.get_full_name())
+        .with_pull_policy(PullPolicy::Never)
+        .with_command(&["cat", "/seed"])
+        .create()
+        .await?
+        .run_to_completion()
+        .await?;
+    assert_eq!(result.stdout.trim(), "seeded");
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_remove_leftovers_of_crashed_sessions() -> Result<(), Error> {
+    let mut labels = Session::current().labels();
+    labels.insert(SESSION_ID_LABEL.to_string(), "crashed-session".to_string());
+    // no process can have this pid since it is above the kernel pid_max limit
+    labels.insert(SESSION_PID_LABEL.to_string(), "4294967295".to_string());
+    let docker = DockerClient::default();
+    docker
+        .create_network(
+            &NetworkCreateOpts::builder("testcontainers-crashed-session")
+                .labels(labels)
+                .build(),
+        )
+        .await?;
+    let removed = Session::current().cleanup_orphans_with(&docker).await?;
+    assert!(removed.contains(&"crashed-session".to_string()));
+    assert!(docker
+        .list_labeled(SESSION_ID_LABEL)
+        .await?
+        .iter()
+        .all(|labels| {
+            labels.get(SESSION_ID_LABEL).map(String::as_str) != Some("crashed-session")
+        }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_start_docker_compose_stack() -> Result<(), Error> {
+    let project_dir = std::env::temp_dir().join("testcontainers-compose");
+    std::fs::create_dir_all(&project_dir)?;
+    let compose_file = project_dir.join("docker-compose.yml");
+    std::fs::write(
+        &compose_file,
+        "services:\n  db:\n    image: postgres:latest\n    environment:\n      POSTGRES_PASSWORD: ${DB_PASSWORD}\n    ports:\n      - \"5432\"\n",
+    )?;
+    let compose = DockerCompose::new(&compose_file)
+        .add_env("DB_PASSWORD", "secret")
+        .with_wait_strategy("db", ReadyStrategy::PortListening("5432/tcp".to_string()))
+        .start()
+        .await?;
+    let host = compose.get_service_host("db").unwrap();
+    let port = compose.get_service_host_port("db", 5432).unwrap();
+    let (client, connection) = tokio_postgres::connect(
+        &format!("host={host} port={port} user=postgres password=secret"),
+        NoTls,
+    )
+    .await
+    .unwrap();
+    tokio::spawn(connection);
+    client.simple_query("SELECT 1").await.unwrap();
+    compose.down().await?;
+    Ok(())
+}
@@ -0,0 +1 @@
+CREATE TABLE users (name VARCHAR(64) NOT NULL);